@@ -0,0 +1,118 @@
+/// How the address of a base address is read from the code matching its
+/// pattern.
+#[derive(Debug, Clone, Copy)]
+pub enum AobKind {
+    /// The address of the match itself.
+    Direct,
+    /// The RIP-relative address at `offset` from the match, relative to the
+    /// instruction ending at `deref_offset`.
+    Indirect { offset: usize, deref_offset: usize },
+}
+
+/// Array-of-bytes pattern of a base address.
+#[derive(Debug)]
+pub struct Aob {
+    pub name: &'static str,
+    /// Alternative patterns, tried in order.
+    pub patterns: &'static [&'static str],
+    pub kind: AobKind,
+}
+
+impl Aob {
+    /// Name of the field of the generated `BaseAddresses`.
+    pub fn symbol(&self) -> String {
+        let mut symbol = String::new();
+        for c in self.name.chars() {
+            if c.is_ascii_uppercase() && !symbol.is_empty() {
+                symbol.push('_');
+            }
+            symbol.push(c.to_ascii_lowercase());
+        }
+        symbol
+    }
+}
+
+const fn indirect(
+    name: &'static str,
+    patterns: &'static [&'static str],
+    offset: usize,
+    deref_offset: usize,
+) -> Aob {
+    Aob { name, patterns, kind: AobKind::Indirect { offset, deref_offset } }
+}
+
+const fn direct(name: &'static str, patterns: &'static [&'static str]) -> Aob {
+    Aob { name, patterns, kind: AobKind::Direct }
+}
+
+/// Patterns of the base addresses. The codegen resolves them in the
+/// executable of every known game version, and they are scanned for at
+/// runtime when the version is unknown.
+pub const AOBS: &[Aob] = &[
+    indirect("Quitout", &["48 8B 05 ?? ?? ?? ?? 48 63 C9 89 54 88 20 C3"], 3, 7),
+    indirect("RenderWorld", &["80 3D ?? ?? ?? ?? 00 0F 10 00 0F 11 45 D0"], 2, 7),
+    indirect("DebugRender", &["44 0F B6 3D ?? ?? ?? ?? 0F 29 74 24 20 0F 28 F1 E8"], 4, 8),
+    indirect(
+        "Igt",
+        &["48 8B 0D ?? ?? ?? ?? 0F 28 C6 F3 0F 59 05 ?? ?? ?? ?? F3 48 0F 2C C0 01 81 ?? ?? ?? ??"],
+        3,
+        7,
+    ),
+    indirect(
+        "PlayerPosition",
+        &["48 83 3D ?? ?? ?? ?? 00 0F 84 ?? ?? ?? ?? F3 41 0F 10 47 78 F3 0F 5C C7"],
+        3,
+        8,
+    ),
+    indirect("DebugFlags", &["80 3D ?? ?? ?? ?? 00 75 08 32 C0 48 83 C4 20"], 2, 7),
+    indirect(
+        "ShowCursor",
+        &["40 38 3D ?? ?? ?? ?? 0F B6 DB 0F 44 DF 84 DB 0F 94 C3 83 7D 40 FF"],
+        3,
+        7,
+    ),
+    direct("NoLogo", &[
+        "74 30 48 8D 54 24 30 48 8B CD E8 ?? ?? ?? ?? 90 BB 01 00 00 00 89 5C 24 20 44 0F B6 4E 04"
+    ]),
+    direct("FontPatch", &["48 8b fa 49 8b f0 48 8b d9"]),
+    indirect(
+        "DebugShow",
+        &["48 8B 05 ?? ?? ?? ?? 48 8B D9 48 85 C0 75 2E 48 8D 0D ?? ?? ?? ?? E8 ?? ?? ?? ?? 4C \
+           8B C8 4C 8D 05 ?? ?? ?? ?? BA ?? ?? ?? ?? 48 8D 0D ?? ?? ?? ?? E8 ?? ?? ?? ?? 48 8B \
+           05 ?? ?? ?? ?? 48 8B 80 ?? ?? ?? ?? 48 85 C0 75 07 48 8B 83 ?? ?? ?? ?? 48 83 C4 20 \
+           5B C3"],
+        3,
+        7,
+    ),
+    indirect(
+        "GrappleDebug",
+        &["48 8B 05 ?? ?? ?? ?? 48 85 C0 75 2E 48 8D 0D ?? ?? ?? ?? E8 ?? ?? ?? ?? 4C 8B C8 4C \
+           8D 05 ?? ?? ?? ?? BA ?? ?? ?? ?? 48 8D 0D ?? ?? ?? ?? E8 ?? ?? ?? ?? 48 8B 05 ?? ?? \
+           ?? ?? 48 8B 80 ?? ?? ?? ?? 48 8D 54 24 50 48 8D 4C 24 40 48 8B 58 08 E8 ?? ?? ?? ?? \
+           4C 8D 4C 24 40 C7 44 24 28 ?? ?? ?? ?? 4C 8D 84 24 ?? ?? ?? ?? F3 0F 11 74 24 20"],
+        3,
+        7,
+    ),
+    indirect(
+        "Fps",
+        &["48 8B 0D ?? ?? ?? ?? 48 85 C9 75 2E 48 8D 0D ?? ?? ?? ?? E8 ?? ?? ?? ?? 4C 8B C8 4C \
+           8D 05 ?? ?? ?? ?? BA ?? ?? ?? ?? 48 8D 0D ?? ?? ?? ?? E8 ?? ?? ?? ?? 48 8B 0D ?? ?? \
+           ?? ?? E8 ?? ?? ?? ?? 84 C0 0F 94 C0 48 83 C4 28 C3 CC CC CC 40 53 48 83 EC 20 48 8B \
+           D9 48 8B 0D ?? ?? ?? ?? 48 85 C9"],
+        3,
+        7,
+    ),
+    indirect(
+        "EventFlagMan",
+        &["48 8B 0D ?? ?? ?? ?? 48 89 5C 24 50 48 89 6C 24 58 48 89 74 24 60"],
+        3,
+        7,
+    ),
+    indirect("GameMan", &["48 8B 15 ?? ?? ?? ?? 41 B0 01 48 8B CB 48 81 C2 10 0E 00 00"], 3, 7),
+    indirect(
+        "FieldArea",
+        &["48 8B 0D ?? ?? ?? ?? 48 85 C9 74 26 44 8B 41 28 48 8D 54 24 40"],
+        3,
+        7,
+    ),
+];
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use widestring::U16CString;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{HMODULE, MAX_PATH};
+use windows::Win32::System::Diagnostics::Debug::{
+    FlushInstructionCache, ReadProcessMemory, WriteProcessMemory,
+};
+use windows::Win32::System::LibraryLoader::{
+    GetModuleFileNameW, GetModuleHandleExW, GetModuleHandleW,
+    GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS, GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+};
+use windows::Win32::System::Memory::{
+    VirtualProtect, PAGE_EXECUTE_READWRITE, PAGE_PROTECTION_FLAGS,
+};
+use windows::Win32::System::Threading::GetCurrentProcess;
+
+/// Address space a [`PointerChain`](crate::memedit::PointerChain) is
+/// evaluated against.
+///
+/// Implementations must not crash on invalid addresses: a read or write
+/// that can't be performed reports `false` and leaves the rest of the
+/// address space untouched.
+pub trait MemoryBackend: Clone + Send + Sync + 'static {
+    /// Reads `buf.len()` bytes starting at `addr` into `buf`.
+    fn read_bytes(&self, addr: usize, buf: &mut [u8]) -> bool;
+
+    /// Writes the contents of `buf` starting at `addr`.
+    fn write_bytes(&self, addr: usize, buf: &[u8]) -> bool;
+
+    /// Writes the contents of `buf` starting at `addr`, which lies in a
+    /// page of executable code that is not writable by default.
+    fn write_code(&self, addr: usize, buf: &[u8]) -> bool {
+        self.write_bytes(addr, buf)
+    }
+
+    /// Base address of the module with the given file name, if loaded.
+    fn module_base(&self, _name: &str) -> Option<usize> {
+        None
+    }
+
+    /// File name and base address of the module containing `addr`.
+    fn module_at(&self, _addr: usize) -> Option<(String, usize)> {
+        None
+    }
+}
+
+/// The address space of the process the library is loaded in.
+///
+/// Relies on `ReadProcessMemory`/`WriteProcessMemory` instead of pointer
+/// dereferencing for crash safety.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CurrentProcess;
+
+impl MemoryBackend for CurrentProcess {
+    fn read_bytes(&self, addr: usize, buf: &mut [u8]) -> bool {
+        unsafe {
+            ReadProcessMemory(
+                GetCurrentProcess(),
+                addr as _,
+                buf.as_mut_ptr() as _,
+                buf.len(),
+                None,
+            )
+            .is_ok()
+        }
+    }
+
+    fn write_bytes(&self, addr: usize, buf: &[u8]) -> bool {
+        unsafe {
+            WriteProcessMemory(GetCurrentProcess(), addr as _, buf.as_ptr() as _, buf.len(), None)
+                .is_ok()
+        }
+    }
+
+    fn write_code(&self, addr: usize, buf: &[u8]) -> bool {
+        let mut old = PAGE_PROTECTION_FLAGS(0);
+        unsafe {
+            if VirtualProtect(addr as _, buf.len(), PAGE_EXECUTE_READWRITE, &mut old).is_err() {
+                return false;
+            }
+            let written = self.write_bytes(addr, buf);
+            VirtualProtect(addr as _, buf.len(), old, &mut old).ok();
+            FlushInstructionCache(GetCurrentProcess(), Some(addr as _), buf.len()).ok();
+            written
+        }
+    }
+
+    fn module_base(&self, name: &str) -> Option<usize> {
+        let name = U16CString::from_str(name).ok()?;
+        unsafe { GetModuleHandleW(PCWSTR(name.as_ptr())) }.ok().map(|module| module.0 as usize)
+    }
+
+    fn module_at(&self, addr: usize) -> Option<(String, usize)> {
+        let mut module = HMODULE::default();
+        unsafe {
+            GetModuleHandleExW(
+                GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS
+                    | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+                PCWSTR(addr as *const u16),
+                &mut module,
+            )
+        }
+        .ok()?;
+
+        let mut buf = vec![0u16; MAX_PATH as usize];
+        let len = unsafe { GetModuleFileNameW(Some(module), &mut buf) } as usize;
+        let path = String::from_utf16_lossy(&buf[..len]);
+        let name = path.rsplit('\\').next().unwrap_or(&path).to_string();
+        Some((name, module.0 as usize))
+    }
+}
+
+/// In-memory fake address space, for exercising pointer chains without a
+/// running game.
+///
+/// Only bytes that have been explicitly mapped with [`FakeMemory::map`] or
+/// [`FakeMemory::put`] can be read or written; any access touching an
+/// unmapped byte fails, like an access to an unmapped page would. Clones
+/// share the same address space.
+#[derive(Debug, Default, Clone)]
+pub struct FakeMemory {
+    bytes: Arc<Mutex<HashMap<usize, u8>>>,
+    modules: Arc<Mutex<Vec<(String, usize, usize)>>>,
+}
+
+impl FakeMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `bytes` at `addr`, overwriting anything previously mapped there.
+    pub fn map(&self, addr: usize, bytes: &[u8]) {
+        let mut memory = self.bytes.lock();
+        for (i, &byte) in bytes.iter().enumerate() {
+            memory.insert(addr + i, byte);
+        }
+    }
+
+    /// Maps the in-memory representation of `value` at `addr`.
+    pub fn put<T: Copy>(&self, addr: usize, value: T) {
+        let bytes = unsafe {
+            std::slice::from_raw_parts(&value as *const T as *const u8, std::mem::size_of::<T>())
+        };
+        self.map(addr, bytes);
+    }
+
+    /// Registers a module named `name`, spanning `size` bytes from `base`.
+    /// Its memory still has to be mapped to be accessible.
+    pub fn add_module(&self, name: &str, base: usize, size: usize) {
+        self.modules.lock().push((name.to_string(), base, size));
+    }
+
+    /// Reads back a value of type `T` at `addr`, if it is fully mapped.
+    pub fn get<T: Copy>(&self, addr: usize) -> Option<T> {
+        let mut value = std::mem::MaybeUninit::<T>::uninit();
+        let buf = unsafe {
+            std::slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, std::mem::size_of::<T>())
+        };
+        self.read_bytes(addr, buf).then(|| unsafe { value.assume_init() })
+    }
+}
+
+impl MemoryBackend for FakeMemory {
+    fn read_bytes(&self, addr: usize, buf: &mut [u8]) -> bool {
+        let memory = self.bytes.lock();
+        for (i, byte) in buf.iter_mut().enumerate() {
+            match memory.get(&(addr + i)) {
+                Some(&b) => *byte = b,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    fn write_bytes(&self, addr: usize, buf: &[u8]) -> bool {
+        let mut memory = self.bytes.lock();
+        if !(addr..addr + buf.len()).all(|a| memory.contains_key(&a)) {
+            return false;
+        }
+        for (i, &byte) in buf.iter().enumerate() {
+            memory.insert(addr + i, byte);
+        }
+        true
+    }
+
+    fn module_base(&self, name: &str) -> Option<usize> {
+        self.modules
+            .lock()
+            .iter()
+            .find(|(module, ..)| module.eq_ignore_ascii_case(name))
+            .map(|&(_, base, _)| base)
+    }
+
+    fn module_at(&self, addr: usize) -> Option<(String, usize)> {
+        self.modules
+            .lock()
+            .iter()
+            .find(|&&(_, base, size)| (base..base + size).contains(&addr))
+            .map(|(name, base, _)| (name.clone(), *base))
+    }
+}
@@ -1,7 +1,7 @@
 // **********************************
 // *** AUTOGENERATED, DO NOT EDIT ***
 // **********************************
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct BaseAddresses {
     pub quitout: usize,
     pub render_world: usize,
@@ -15,6 +15,9 @@ pub struct BaseAddresses {
     pub debug_show: usize,
     pub grapple_debug: usize,
     pub fps: usize,
+    pub event_flag_man: usize,
+    pub game_man: usize,
+    pub field_area: usize,
 }
 
 impl BaseAddresses {
@@ -32,6 +35,9 @@ impl BaseAddresses {
             debug_show: self.debug_show + base,
             grapple_debug: self.grapple_debug + base,
             fps: self.fps + base,
+            event_flag_man: self.event_flag_man + base,
+            game_man: self.game_man + base,
+            field_area: self.field_area + base,
         }
     }
 }
@@ -100,6 +106,9 @@ pub const BASE_ADDRESSES_1_02_0: BaseAddresses = BaseAddresses {
     debug_show: 0x3b67f98,
     grapple_debug: 0x3b5b240,
     fps: 0x3c8c2c8,
+    event_flag_man: 0x0,
+    game_man: 0x0,
+    field_area: 0x0,
 };
 
 pub const BASE_ADDRESSES_1_03_0: BaseAddresses = BaseAddresses {
@@ -115,6 +124,9 @@ pub const BASE_ADDRESSES_1_03_0: BaseAddresses = BaseAddresses {
     debug_show: 0x3b68fd8,
     grapple_debug: 0x3b5c280,
     fps: 0x3c8d308,
+    event_flag_man: 0x0,
+    game_man: 0x0,
+    field_area: 0x0,
 };
 
 pub const BASE_ADDRESSES_1_04_0: BaseAddresses = BaseAddresses {
@@ -130,6 +142,9 @@ pub const BASE_ADDRESSES_1_04_0: BaseAddresses = BaseAddresses {
     debug_show: 0x3b68fd8,
     grapple_debug: 0x3b5c280,
     fps: 0x3c8d308,
+    event_flag_man: 0x0,
+    game_man: 0x0,
+    field_area: 0x0,
 };
 
 pub const BASE_ADDRESSES_1_05_0: BaseAddresses = BaseAddresses {
@@ -145,6 +160,9 @@ pub const BASE_ADDRESSES_1_05_0: BaseAddresses = BaseAddresses {
     debug_show: 0x3d7a2e8,
     grapple_debug: 0x3d6d5a0,
     fps: 0x3e9f6a8,
+    event_flag_man: 0x0,
+    game_man: 0x0,
+    field_area: 0x0,
 };
 
 pub const BASE_ADDRESSES_1_06_0: BaseAddresses = BaseAddresses {
@@ -160,4 +178,71 @@ pub const BASE_ADDRESSES_1_06_0: BaseAddresses = BaseAddresses {
     debug_show: 0x3d7a388,
     grapple_debug: 0x3d6d640,
     fps: 0x3e9f748,
+    event_flag_man: 0x0,
+    game_man: 0x0,
+    field_area: 0x0,
 };
+impl BaseAddresses {
+    /// Names of the base addresses, as accepted by [`BaseAddresses::get`].
+    pub const SYMBOLS: &'static [&'static str] = &[
+        "quitout",
+        "render_world",
+        "debug_render",
+        "igt",
+        "player_position",
+        "debug_flags",
+        "show_cursor",
+        "no_logo",
+        "font_patch",
+        "debug_show",
+        "grapple_debug",
+        "fps",
+        "event_flag_man",
+        "game_man",
+        "field_area",
+    ];
+
+    /// Looks up a base address by the name of its field, e.g.
+    /// `"player_position"`.
+    pub fn get(&self, name: &str) -> Option<usize> {
+        match name {
+            "quitout" => Some(self.quitout),
+            "render_world" => Some(self.render_world),
+            "debug_render" => Some(self.debug_render),
+            "igt" => Some(self.igt),
+            "player_position" => Some(self.player_position),
+            "debug_flags" => Some(self.debug_flags),
+            "show_cursor" => Some(self.show_cursor),
+            "no_logo" => Some(self.no_logo),
+            "font_patch" => Some(self.font_patch),
+            "debug_show" => Some(self.debug_show),
+            "grapple_debug" => Some(self.grapple_debug),
+            "fps" => Some(self.fps),
+            "event_flag_man" => Some(self.event_flag_man),
+            "game_man" => Some(self.game_man),
+            "field_area" => Some(self.field_area),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut usize> {
+        match name {
+            "quitout" => Some(&mut self.quitout),
+            "render_world" => Some(&mut self.render_world),
+            "debug_render" => Some(&mut self.debug_render),
+            "igt" => Some(&mut self.igt),
+            "player_position" => Some(&mut self.player_position),
+            "debug_flags" => Some(&mut self.debug_flags),
+            "show_cursor" => Some(&mut self.show_cursor),
+            "no_logo" => Some(&mut self.no_logo),
+            "font_patch" => Some(&mut self.font_patch),
+            "debug_show" => Some(&mut self.debug_show),
+            "grapple_debug" => Some(&mut self.grapple_debug),
+            "fps" => Some(&mut self.fps),
+            "event_flag_man" => Some(&mut self.event_flag_man),
+            "game_man" => Some(&mut self.game_man),
+            "field_area" => Some(&mut self.field_area),
+            _ => None,
+        }
+    }
+}
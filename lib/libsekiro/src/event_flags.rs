@@ -0,0 +1,112 @@
+use crate::backend::{CurrentProcess, MemoryBackend};
+use crate::memedit::{PointerChain, PointerChainError};
+use crate::pointers::base_module_address;
+use crate::scan::CURRENT_BASE_ADDRESSES;
+
+/// Offset of the per-group flag tables in `SprjEventFlagMan`.
+const GROUPS_OFFSET: usize = 0x218;
+/// Size of an entry of the group array.
+const GROUP_SIZE: usize = 0x18;
+/// Size of the bitfield holding a block of 1000 flags.
+const BLOCK_SIZE: usize = 0x80;
+
+/// Reads and writes the game's event flags, which track progression: bosses
+/// defeated, idols unlocked, NPC quest steps...
+///
+/// A flag ID such as `11105800` is split into its group (the ten-millions
+/// digit), its block of 1000 flags within the group and its index within
+/// the block. Each block is a bitfield of 32-bit words, whose bits are
+/// numbered starting from the most significant one.
+#[derive(Debug, Clone)]
+pub struct EventFlags<M: MemoryBackend = CurrentProcess> {
+    backend: M,
+    /// Absolute address of the static pointer to `SprjEventFlagMan`, or 0 if
+    /// it couldn't be found.
+    man: usize,
+}
+
+impl Default for EventFlags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventFlags {
+    pub fn new() -> Self {
+        let base_addresses = CURRENT_BASE_ADDRESSES.with_module_base_addr(base_module_address());
+        Self::with_backend(CurrentProcess, base_addresses.event_flag_man)
+    }
+}
+
+impl<M: MemoryBackend> EventFlags<M> {
+    /// `man` is the absolute address of the static pointer to
+    /// `SprjEventFlagMan`.
+    pub fn with_backend(backend: M, man: usize) -> Self {
+        EventFlags { backend, man }
+    }
+
+    /// Returns the address of the word holding the flag, and its mask.
+    fn locate(&self, id: u32) -> Result<(usize, u32), PointerChainError> {
+        let id = id as usize;
+        let group = id / 10_000_000;
+        let block = id / 1000 % 10_000;
+        let index = id % 1000;
+
+        let table: PointerChain<usize, M> = PointerChain::with_backend(self.backend.clone(), &[
+            self.man,
+            GROUPS_OFFSET + group * GROUP_SIZE,
+        ]);
+        let address = table.read()? + block * BLOCK_SIZE + 4 * (index / 32);
+        Ok((address, 1 << (31 - index % 32)))
+    }
+
+    fn word(&self, address: usize) -> PointerChain<u32, M> {
+        PointerChain::with_backend(self.backend.clone(), &[address])
+    }
+
+    pub fn get(&self, id: u32) -> Result<bool, PointerChainError> {
+        let (address, mask) = self.locate(id)?;
+        Ok(self.word(address).read()? & mask != 0)
+    }
+
+    pub fn set(&self, id: u32, value: bool) -> Result<(), PointerChainError> {
+        let (address, mask) = self.locate(id)?;
+        let word = self.word(address);
+        let current = word.read()?;
+        word.write(if value { current | mask } else { current & !mask })
+    }
+
+    /// Flips the flag, returning its new state.
+    pub fn toggle(&self, id: u32) -> Result<bool, PointerChainError> {
+        let value = !self.get(id)?;
+        self.set(id, value)?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FakeMemory;
+
+    #[test]
+    fn test_event_flags() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 0x2000usize);
+        memory.put(0x2000 + GROUPS_OFFSET + GROUP_SIZE, 0x10_0000usize);
+        // Flag 11105800: block 1105, index 800, i.e. word 25, bit 0.
+        let word = 0x10_0000 + 1105 * BLOCK_SIZE + 4 * 25;
+        memory.put(word, 0u32);
+
+        let flags = EventFlags::with_backend(memory.clone(), 0x1000);
+        assert_eq!(flags.get(11105800), Ok(false));
+        assert_eq!(flags.set(11105800, true), Ok(()));
+        assert_eq!(memory.get::<u32>(word), Some(0x8000_0000));
+        assert_eq!(flags.get(11105800), Ok(true));
+        assert_eq!(flags.toggle(11105800), Ok(false));
+        assert_eq!(memory.get::<u32>(word), Some(0));
+
+        let unmapped = EventFlags::with_backend(memory, 0);
+        assert!(matches!(unmapped.get(11105800), Err(PointerChainError::Deref { level: 0, .. })));
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::backend::{CurrentProcess, MemoryBackend};
+use crate::memedit::{PointerChain, PointerChainError};
+
+#[derive(Debug, Default)]
+struct State {
+    /// Game speed to restore when resuming or stepping, set while paused.
+    paused: Option<f32>,
+    steps_left: u32,
+}
+
+/// Pauses the game by setting the global game speed to 0, and steps it one
+/// frame at a time by restoring the speed for single frames.
+///
+/// [`FrameAdvance::tick`] must be called once per frame. Clones share the
+/// same state.
+#[derive(Debug, Clone)]
+pub struct FrameAdvance<M: MemoryBackend = CurrentProcess> {
+    game_speed: PointerChain<f32, M>,
+    state: Arc<Mutex<State>>,
+}
+
+impl<M: MemoryBackend> FrameAdvance<M> {
+    pub fn new(game_speed: PointerChain<f32, M>) -> Self {
+        FrameAdvance { game_speed, state: Default::default() }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().paused.is_some()
+    }
+
+    pub fn pause(&self) -> Result<(), PointerChainError> {
+        let mut state = self.state.lock();
+        if state.paused.is_none() {
+            let speed = self.game_speed.read()?;
+            state.paused = Some(if speed > 0. { speed } else { 1. });
+            state.steps_left = 0;
+            self.game_speed.write(0.)?;
+        }
+        Ok(())
+    }
+
+    pub fn resume(&self) -> Result<(), PointerChainError> {
+        let mut state = self.state.lock();
+        match state.paused.take() {
+            Some(speed) => self.game_speed.write(speed),
+            None => Ok(()),
+        }
+    }
+
+    pub fn toggle_pause(&self) -> Result<(), PointerChainError> {
+        if self.is_paused() {
+            self.resume()
+        } else {
+            self.pause()
+        }
+    }
+
+    /// Lets the game run for `frames` more frames, pausing it first if it
+    /// isn't already.
+    pub fn step(&self, frames: u32) -> Result<(), PointerChainError> {
+        self.pause()?;
+        self.state.lock().steps_left += frames;
+        Ok(())
+    }
+
+    /// Applies the game speed for the coming frame. Returns whether the game
+    /// advances during it, i.e. whether it's running or being stepped.
+    pub fn tick(&self) -> Result<bool, PointerChainError> {
+        let mut state = self.state.lock();
+        let Some(speed) = state.paused else {
+            return Ok(true);
+        };
+
+        if state.steps_left > 0 {
+            state.steps_left -= 1;
+            self.game_speed.write(speed)?;
+            Ok(true)
+        } else {
+            self.game_speed.write(0.)?;
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FakeMemory;
+
+    #[test]
+    fn test_frame_advance() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 0.5f32);
+        let frame_advance =
+            FrameAdvance::new(PointerChain::with_backend(memory.clone(), &[0x1000]));
+
+        assert_eq!(frame_advance.tick(), Ok(true));
+        frame_advance.pause().unwrap();
+        assert_eq!(memory.get::<f32>(0x1000), Some(0.));
+        assert_eq!(frame_advance.tick(), Ok(false));
+
+        frame_advance.step(2).unwrap();
+        let ticks: Vec<_> = (0..3).map(|_| frame_advance.tick().unwrap()).collect();
+        assert_eq!(ticks, [true, true, false]);
+        assert_eq!(memory.get::<f32>(0x1000), Some(0.));
+
+        frame_advance.toggle_pause().unwrap();
+        assert!(!frame_advance.is_paused());
+        assert_eq!(memory.get::<f32>(0x1000), Some(0.5));
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::backend::MemoryBackend;
+use crate::memedit::{PointerChain, PointerChainError};
+
+struct FrozenValue {
+    label: String,
+    value: String,
+    write: Box<dyn Fn() -> Result<(), PointerChainError> + Send + Sync>,
+    last_error: Option<PointerChainError>,
+}
+
+/// A currently frozen value, as shown to the user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrozenEntry {
+    pub label: String,
+    pub value: String,
+    /// Set if the last attempt at writing the value back failed, e.g.
+    /// because the game is on a loading screen.
+    pub error: Option<PointerChainError>,
+}
+
+/// Holds values fixed by writing them back to their pointer chains every
+/// time [`Freezer::tick`] is called, i.e. once per frame.
+///
+/// Values are identified by their label: freezing a label again replaces
+/// the previous value. Clones share the same set of frozen values.
+#[derive(Clone, Default)]
+pub struct Freezer(Arc<Mutex<Vec<FrozenValue>>>);
+
+impl Freezer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Freezes the datum pointed to by `chain` to `value`.
+    pub fn freeze<T, M>(&self, label: &str, chain: PointerChain<T, M>, value: T)
+    where
+        T: Copy + Debug + Send + Sync + 'static,
+        M: MemoryBackend,
+    {
+        let frozen = FrozenValue {
+            label: label.to_string(),
+            value: format!("{value:?}"),
+            write: Box::new(move || chain.write(value)),
+            last_error: None,
+        };
+
+        let mut values = self.0.lock();
+        match values.iter_mut().find(|v| v.label == label) {
+            Some(v) => *v = frozen,
+            None => values.push(frozen),
+        }
+    }
+
+    /// Freezes the datum pointed to by `chain` to its current value.
+    pub fn freeze_current<T, M>(
+        &self,
+        label: &str,
+        chain: PointerChain<T, M>,
+    ) -> Result<(), PointerChainError>
+    where
+        T: Copy + Debug + Send + Sync + 'static,
+        M: MemoryBackend,
+    {
+        let value = chain.read()?;
+        self.freeze(label, chain, value);
+        Ok(())
+    }
+
+    /// Stops writing back the value with the given label. Returns whether
+    /// it was frozen.
+    pub fn unfreeze(&self, label: &str) -> bool {
+        let mut values = self.0.lock();
+        let len = values.len();
+        values.retain(|v| v.label != label);
+        values.len() != len
+    }
+
+    pub fn unfreeze_all(&self) {
+        self.0.lock().clear();
+    }
+
+    pub fn is_frozen(&self, label: &str) -> bool {
+        self.0.lock().iter().any(|v| v.label == label)
+    }
+
+    /// Lists the frozen values, in the order they were frozen.
+    pub fn entries(&self) -> Vec<FrozenEntry> {
+        self.0
+            .lock()
+            .iter()
+            .map(|v| FrozenEntry {
+                label: v.label.clone(),
+                value: v.value.clone(),
+                error: v.last_error,
+            })
+            .collect()
+    }
+
+    /// Writes back all frozen values.
+    pub fn tick(&self) {
+        for v in self.0.lock().iter_mut() {
+            v.last_error = (v.write)().err();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FakeMemory;
+
+    #[test]
+    fn test_freezer() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 1.0f32);
+        let chain: PointerChain<f32, _> = PointerChain::with_backend(memory.clone(), &[0x1000]);
+
+        let freezer = Freezer::new();
+        freezer.freeze("Speed", chain.clone(), 0.5);
+        freezer.tick();
+        assert_eq!(memory.get::<f32>(0x1000), Some(0.5));
+
+        memory.put(0x1000, 1.0f32);
+        freezer.tick();
+        assert_eq!(memory.get::<f32>(0x1000), Some(0.5));
+        assert_eq!(freezer.entries(), vec![FrozenEntry {
+            label: "Speed".to_string(),
+            value: "0.5".to_string(),
+            error: None
+        }]);
+
+        assert!(freezer.unfreeze("Speed"));
+        assert!(!freezer.is_frozen("Speed"));
+        memory.put(0x1000, 1.0f32);
+        freezer.tick();
+        assert_eq!(memory.get::<f32>(0x1000), Some(1.0));
+
+        let unmapped: PointerChain<f32, _> = PointerChain::with_backend(memory, &[0x2000]);
+        assert!(freezer.freeze_current("Unmapped", unmapped.clone()).is_err());
+        freezer.freeze("Unmapped", unmapped, 0.5);
+        freezer.tick();
+        assert_eq!(freezer.entries()[0].error, Some(PointerChainError::Write { address: 0x2000 }));
+    }
+}
@@ -1,11 +1,31 @@
+pub mod aobs;
+pub mod backend;
 pub mod codegen;
+pub mod event_flags;
+pub mod frame_advance;
+pub mod freeze;
 pub mod memedit;
+pub mod numeric;
 pub mod pointers;
+pub mod remote;
+pub mod scan;
+pub mod snapshot;
 pub mod version;
+pub mod warp;
 
 pub mod prelude {
+    pub use crate::aobs::*;
+    pub use crate::backend::*;
     pub use crate::codegen::*;
+    pub use crate::event_flags::*;
+    pub use crate::frame_advance::*;
+    pub use crate::freeze::*;
     pub use crate::memedit::*;
+    pub use crate::numeric::*;
     pub use crate::pointers::*;
+    pub use crate::remote::*;
+    pub use crate::scan::*;
+    pub use crate::snapshot::*;
     pub use crate::version::*;
+    pub use crate::warp::*;
 }
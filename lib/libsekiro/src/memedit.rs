@@ -1,9 +1,11 @@
 use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
 use std::ops::{BitAnd, BitOr, BitXor, Not};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use windows::Win32::Foundation::HANDLE;
-use windows::Win32::System::Diagnostics::Debug::{ReadProcessMemory, WriteProcessMemory};
-use windows::Win32::System::Threading::GetCurrentProcess;
+use crate::backend::{CurrentProcess, MemoryBackend};
+use crate::version::Version;
 
 /// Wraps CheatEngine's concept of pointer with nested offsets. Evaluates,
 /// if the evaluation does not fail, to a mutable pointer of type `T`.
@@ -12,120 +14,245 @@ use windows::Win32::System::Threading::GetCurrentProcess;
 /// base pointer, then recursively reading the next memory address in the
 /// chain at an offset from there. For example,
 ///
-/// ```
+/// ```ignore
 /// PointerChain::<T>::new(&[a, b, c, d, e])
 /// ```
 ///
 /// evaluates to
 ///
-/// ```
+/// ```ignore
 /// *(*(*(*(*a + b) + c) + d) + e)
 /// ```
 ///
 /// This is useful for managing reverse engineered structures which are not
 /// fully known.
+///
+/// All memory accesses go through a [`MemoryBackend`], which defaults to the
+/// address space of the current process.
+///
+/// Chains can be written and displayed with Cheat Engine's syntax, e.g.
+/// `sekiro.exe+3B67DF0 -> 48 -> 28 -> 80`, where all numbers are hexadecimal
+/// and the module base is resolved by the backend.
 #[derive(Clone)]
-pub struct PointerChain<T> {
-    proc: HANDLE,
-    base: *mut T,
+pub struct PointerChain<T, M: MemoryBackend = CurrentProcess> {
+    backend: M,
+    base: usize,
     offsets: Vec<usize>,
+    _marker: PhantomData<fn() -> T>,
 }
-unsafe impl<T> Send for PointerChain<T> {}
-unsafe impl<T> Sync for PointerChain<T> {}
 
 impl<T> PointerChain<T> {
     /// Creates a new pointer chain given an array of addresses.
     pub fn new(chain: &[usize]) -> PointerChain<T> {
+        PointerChain::with_backend(CurrentProcess, chain)
+    }
+}
+
+impl<T> FromStr for PointerChain<T> {
+    type Err = PointerChainParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PointerChain::parse_with_backend(CurrentProcess, s)
+    }
+}
+
+impl<T, M: MemoryBackend> PointerChain<T, M> {
+    /// Creates a new pointer chain given an array of addresses, evaluated
+    /// against the given memory backend.
+    pub fn with_backend(backend: M, chain: &[usize]) -> PointerChain<T, M> {
         let mut it = chain.iter();
-        let base = *it.next().unwrap() as *mut T;
-        PointerChain {
-            proc: unsafe { GetCurrentProcess() },
-            base,
-            offsets: it.copied().collect(), // it.map(|x| *x).collect(),
-        }
-    }
-
-    fn safe_read(&self, addr: usize, offs: usize) -> Option<usize> {
-        let mut value = 0usize;
-        unsafe {
-            ReadProcessMemory(
-                self.proc,
-                addr as _,
-                &mut value as *mut usize as _,
-                std::mem::size_of::<usize>(),
-                None,
-            )
-            .ok()
-            .map(|_| value + offs)
+        let base = *it.next().unwrap();
+        PointerChain { backend, base, offsets: it.copied().collect(), _marker: PhantomData }
+    }
+
+    /// Parses a chain written like `sekiro.exe+3B67DF0 -> 48 -> 28 -> 80`,
+    /// resolving the module base through `backend`. The base can also be an
+    /// absolute address.
+    pub fn parse_with_backend(backend: M, s: &str) -> Result<Self, PointerChainParseError> {
+        fn parse_hex(s: &str) -> Result<usize, PointerChainParseError> {
+            let digits = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+            usize::from_str_radix(digits, 16)
+                .map_err(|_| PointerChainParseError::InvalidNumber(s.to_string()))
+        }
+
+        let mut parts = s.split("->").map(str::trim);
+        let base = match parts.next().filter(|base| !base.is_empty()) {
+            None => return Err(PointerChainParseError::Empty),
+            Some(base) => match base.split_once('+') {
+                Some((module, offset)) if module.contains('.') => {
+                    let module_base = backend.module_base(module.trim()).ok_or_else(|| {
+                        PointerChainParseError::ModuleNotFound(module.to_string())
+                    })?;
+                    module_base + parse_hex(offset.trim())?
+                },
+                Some((base, offset)) => parse_hex(base.trim())? + parse_hex(offset.trim())?,
+                None => parse_hex(base)?,
+            },
+        };
+        let offsets = parts.map(parse_hex).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(PointerChain { backend, base, offsets, _marker: PhantomData })
+    }
+
+    /// Returns a chain pointing to a datum of type `U` located `offset`
+    /// bytes after the datum pointed to by this chain, e.g. a single
+    /// coordinate of a position vector.
+    pub fn field<U>(&self, offset: usize) -> PointerChain<U, M> {
+        let mut chain = PointerChain {
+            backend: self.backend.clone(),
+            base: self.base,
+            offsets: self.offsets.clone(),
+            _marker: PhantomData,
+        };
+        match chain.offsets.last_mut() {
+            Some(last) => *last += offset,
+            None => chain.base += offset,
+        }
+        chain
+    }
+
+    fn safe_read(
+        &self,
+        level: usize,
+        addr: usize,
+        offs: usize,
+    ) -> Result<usize, PointerChainError> {
+        let mut value = [0u8; std::mem::size_of::<usize>()];
+        if self.backend.read_bytes(addr, &mut value) {
+            Ok(usize::from_ne_bytes(value) + offs)
+        } else {
+            Err(PointerChainError::Deref { level, address: addr })
         }
     }
 
     /// Safely evaluates the pointer chain.
-    /// Relies on `ReadProcessMemory` instead of pointer dereferencing for crash
-    /// safety.  Returns `None` if the evaluation failed.
-    pub fn eval(&self) -> Option<*mut T> {
+    /// Relies on the memory backend instead of pointer dereferencing for crash
+    /// safety. Fails with the level of the chain that couldn't be dereferenced.
+    pub fn eval(&self) -> Result<*mut T, PointerChainError> {
         self.offsets
             .iter()
-            .try_fold(self.base as usize, |addr, &offs| self.safe_read(addr, offs))
+            .enumerate()
+            .try_fold(self.base, |addr, (level, &offs)| self.safe_read(level, addr, offs))
             .map(|addr| addr as *mut T)
     }
 
     /// Evaluates the pointer chain and attempts to read the datum.
-    /// Returns `None` if either the evaluation or the read failed.
-    pub fn read(&self) -> Option<T> {
+    /// Fails if either the evaluation or the read failed.
+    pub fn read(&self) -> Result<T, PointerChainError> {
         let ptr = self.eval()?;
         let mut value: T = unsafe { std::mem::zeroed() };
-        unsafe {
-            ReadProcessMemory(
-                self.proc,
-                ptr as _,
-                &mut value as *mut _ as _,
+        let buf = unsafe {
+            std::slice::from_raw_parts_mut(
+                &mut value as *mut T as *mut u8,
                 std::mem::size_of::<T>(),
-                None,
             )
-            .ok()
-            .map(|_| value)
+        };
+        if self.backend.read_bytes(ptr as usize, buf) {
+            Ok(value)
+        } else {
+            Err(PointerChainError::Read { address: ptr as usize })
         }
     }
 
     /// Evaluates the pointer chain and attempts to write the datum.
-    /// Returns `None` if either the evaluation or the write failed.
-    pub fn write(&self, mut value: T) -> Option<()> {
+    /// Fails if either the evaluation or the write failed.
+    pub fn write(&self, value: T) -> Result<(), PointerChainError> {
         let ptr = self.eval()?;
-        unsafe {
-            WriteProcessMemory(
-                self.proc,
-                ptr as _,
-                &mut value as *mut _ as _,
-                std::mem::size_of::<T>(),
-                None,
-            )
-            .ok()
-            .map(|_| ())
+        let buf = unsafe {
+            std::slice::from_raw_parts(&value as *const T as *const u8, std::mem::size_of::<T>())
+        };
+        if self.backend.write_bytes(ptr as usize, buf) {
+            Ok(())
+        } else {
+            Err(PointerChainError::Write { address: ptr as usize })
+        }
+    }
+}
+
+/// Describes which step of the evaluation of a [`PointerChain`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerChainError {
+    /// The pointer at `address` couldn't be dereferenced. Level 0 is the base
+    /// address, level `n` is the pointer reached by applying the `n`-th
+    /// offset.
+    Deref { level: usize, address: usize },
+    /// The chain was evaluated to `address`, but the datum couldn't be read.
+    Read { address: usize },
+    /// The chain was evaluated to `address`, but the datum couldn't be
+    /// written.
+    Write { address: usize },
+}
+
+impl Display for PointerChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PointerChainError::Deref { level: 0, address } => {
+                write!(f, "couldn't read base address {address:#x}")
+            },
+            PointerChainError::Deref { level, address } => {
+                write!(f, "couldn't read pointer at {address:#x} (offset #{level})")
+            },
+            PointerChainError::Read { address } => write!(f, "couldn't read value at {address:#x}"),
+            PointerChainError::Write { address } => {
+                write!(f, "couldn't write value at {address:#x}")
+            },
         }
     }
 }
 
+impl std::error::Error for PointerChainError {}
+
 #[derive(Clone)]
-pub struct Bitflag<T>(PointerChain<T>, T);
+pub struct Bitflag<T, M: MemoryBackend = CurrentProcess>(PointerChain<T, M>, T);
 
-impl<T: Debug> Debug for PointerChain<T> {
+impl<T, M: MemoryBackend> Display for PointerChain<T, M> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "PointerChain({:?} @ {:p}", self.proc.0, self.base)?;
+        match self.backend.module_at(self.base) {
+            Some((module, module_base)) => write!(f, "{module}+{:X}", self.base - module_base)?,
+            None => write!(f, "{:X}", self.base)?,
+        }
         for o in &self.offsets {
-            write!(f, ", {:x}", o)?;
+            write!(f, " -> {o:X}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, M: MemoryBackend> Debug for PointerChain<T, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PointerChain({self})")
+    }
+}
+
+/// Describes why a string couldn't be parsed into a [`PointerChain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointerChainParseError {
+    Empty,
+    InvalidNumber(String),
+    ModuleNotFound(String),
+}
+
+impl Display for PointerChainParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PointerChainParseError::Empty => write!(f, "empty pointer chain"),
+            PointerChainParseError::InvalidNumber(s) => {
+                write!(f, "\"{s}\" is not a hexadecimal number")
+            },
+            PointerChainParseError::ModuleNotFound(s) => write!(f, "module \"{s}\" not found"),
         }
-        write!(f, ")")
     }
 }
 
-impl<T: Display + Debug> Debug for Bitflag<T> {
+impl std::error::Error for PointerChainParseError {}
+
+impl<T: Display, M: MemoryBackend> Debug for Bitflag<T, M> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Bitflag(bit {} of {:?})", self.1, self.0)
     }
 }
 
-impl<T> Bitflag<T>
+impl<T, M> Bitflag<T, M>
 where
     T: BitXor<Output = T>
         + BitAnd<Output = T>
@@ -133,40 +260,338 @@ where
         + Not<Output = T>
         + PartialEq
         + Copy,
+    M: MemoryBackend,
 {
-    pub fn new(c: PointerChain<T>, mask: T) -> Self {
+    pub fn new(c: PointerChain<T, M>, mask: T) -> Self {
         Bitflag(c, mask)
     }
 
-    pub fn toggle(&self) -> Option<bool> {
-        if let Some(x) = self.0.read() {
-            self.0.write(x ^ self.1);
-            Some(x == self.1)
+    pub fn toggle(&self) -> Result<bool, PointerChainError> {
+        let x = self.0.read()?;
+        self.0.write(x ^ self.1)?;
+        Ok(x == self.1)
+    }
+
+    pub fn get(&self) -> Result<bool, PointerChainError> {
+        self.0.read().map(|x| (x & self.1) == self.1)
+    }
+
+    pub fn set(&self, flag: bool) -> Result<(), PointerChainError> {
+        let x = self.0.read()?;
+        self.0.write(if flag { x | self.1 } else { x & !self.1 })
+    }
+}
+
+/// A patch to the game's code, replacing a known sequence of bytes with
+/// another one of the same length.
+///
+/// The original bytes are checked before applying the patch, and the patched
+/// bytes before reverting it, so that a patch meant for a different game
+/// version never overwrites code it doesn't know about. Only a patch that was
+/// applied through this object gets reverted: if the code was already
+/// patched, e.g. by another tool, it's left as it is.
+pub struct CodePatch<M: MemoryBackend = CurrentProcess> {
+    label: &'static str,
+    backend: M,
+    version: Version,
+    address: usize,
+    original: Vec<u8>,
+    patched: Vec<u8>,
+    applied: AtomicBool,
+}
+
+impl CodePatch {
+    /// Creates a patch for the code of the current process.
+    pub fn new(
+        label: &'static str,
+        version: Version,
+        address: usize,
+        original: &[u8],
+        patched: &[u8],
+    ) -> Self {
+        CodePatch::with_backend(CurrentProcess, label, version, address, original, patched)
+    }
+}
+
+impl<M: MemoryBackend> CodePatch<M> {
+    pub fn with_backend(
+        backend: M,
+        label: &'static str,
+        version: Version,
+        address: usize,
+        original: &[u8],
+        patched: &[u8],
+    ) -> Self {
+        assert_eq!(original.len(), patched.len());
+        CodePatch {
+            label,
+            backend,
+            version,
+            address,
+            original: original.to_vec(),
+            patched: patched.to_vec(),
+            applied: AtomicBool::new(false),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// Whether the patch was applied through this object and not reverted.
+    pub fn is_applied(&self) -> bool {
+        self.applied.load(Ordering::SeqCst)
+    }
+
+    fn read_current(&self) -> Result<Vec<u8>, CodePatchError> {
+        let mut current = vec![0u8; self.original.len()];
+        if self.backend.read_bytes(self.address, &mut current) {
+            Ok(current)
         } else {
-            None
+            Err(self.error(CodePatchErrorKind::Read))
         }
     }
 
-    pub fn get(&self) -> Option<bool> {
-        self.0.read().map(|x| (x & self.1) == self.1)
+    fn error(&self, kind: CodePatchErrorKind) -> CodePatchError {
+        CodePatchError {
+            label: self.label,
+            version: self.version.into(),
+            address: self.address,
+            kind,
+        }
+    }
+
+    /// Checks that the code at the patch address is either the original or
+    /// the patched one.
+    pub fn verify(&self) -> Result<(), CodePatchError> {
+        let current = self.read_current()?;
+        if current == self.original || current == self.patched {
+            Ok(())
+        } else {
+            Err(self
+                .error(CodePatchErrorKind::Mismatch { expected: self.original.clone(), current }))
+        }
     }
 
-    pub fn set(&self, flag: bool) {
-        if let Some(x) = self.0.read() {
-            self.0.write(if flag { x | self.1 } else { x & !self.1 });
+    /// Applies the patch if the original code is in place.
+    pub fn apply(&self) -> Result<(), CodePatchError> {
+        let current = self.read_current()?;
+        if current == self.patched {
+            return Ok(());
         }
+        if current != self.original {
+            return Err(self
+                .error(CodePatchErrorKind::Mismatch { expected: self.original.clone(), current }));
+        }
+        if !self.backend.write_code(self.address, &self.patched) {
+            return Err(self.error(CodePatchErrorKind::Write));
+        }
+        self.applied.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Restores the original code, if the patch was applied through this
+    /// object.
+    pub fn revert(&self) -> Result<(), CodePatchError> {
+        if !self.is_applied() {
+            return Ok(());
+        }
+        let current = self.read_current()?;
+        if current != self.patched {
+            return Err(self
+                .error(CodePatchErrorKind::Mismatch { expected: self.patched.clone(), current }));
+        }
+        if !self.backend.write_code(self.address, &self.original) {
+            return Err(self.error(CodePatchErrorKind::Write));
+        }
+        self.applied.store(false, Ordering::SeqCst);
+        Ok(())
     }
 }
 
+impl<M: MemoryBackend> Debug for CodePatch<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CodePatch({} at {:#x})", self.label, self.address)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodePatchErrorKind {
+    /// The code at the patch address couldn't be read.
+    Read,
+    /// The patch couldn't be written.
+    Write,
+    /// The code at the patch address isn't the expected one, most likely
+    /// because the patch doesn't fit the running game version.
+    Mismatch { expected: Vec<u8>, current: Vec<u8> },
+}
+
+/// Describes why a [`CodePatch`] couldn't be applied or reverted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodePatchError {
+    pub label: &'static str,
+    /// The `major.minor.patch` game version the patch was built for.
+    pub version: (u32, u32, u32),
+    pub address: usize,
+    pub kind: CodePatchErrorKind,
+}
+
+impl Display for CodePatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (maj, min, patch) = self.version;
+        write!(f, "{} patch (game version {maj}.{min:02}.{patch}): ", self.label)?;
+        match &self.kind {
+            CodePatchErrorKind::Read => write!(f, "couldn't read code at {:#x}", self.address),
+            CodePatchErrorKind::Write => write!(f, "couldn't write code at {:#x}", self.address),
+            CodePatchErrorKind::Mismatch { expected, current } => write!(
+                f,
+                "expected {:02x?} at {:#x}, found {:02x?}",
+                expected, self.address, current
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CodePatchError {}
+
+/// Builds a [`PointerChain`] from a list of addresses. Prefix the list with
+/// `backend =>` to evaluate it against a specific [`MemoryBackend`].
 #[macro_export]
 macro_rules! pointer_chain {
-    ($($e:expr),+) => { PointerChain::new(&[$($e,)*]) }
+    ($m:ident => $($e:expr),+) => { PointerChain::with_backend($m.clone(), &[$($e,)*]) };
+    ($($e:expr),+) => { PointerChain::new(&[$($e,)*]) };
 }
 
+/// Builds a [`Bitflag`] from a mask and a list of addresses. Prefix it with
+/// `backend =>` to evaluate it against a specific [`MemoryBackend`].
 #[macro_export]
 macro_rules! bitflag {
-    ($b:expr; $($e:expr),+) => { Bitflag::new(PointerChain::new(&[$($e,)*]), $b) }
+    ($m:ident => $b:expr; $($e:expr),+) => {
+        Bitflag::new(PointerChain::with_backend($m.clone(), &[$($e,)*]), $b)
+    };
+    ($b:expr; $($e:expr),+) => { Bitflag::new(PointerChain::new(&[$($e,)*]), $b) };
 }
 
 pub use bitflag;
 pub use pointer_chain;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FakeMemory;
+
+    #[test]
+    fn test_pointer_chain() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 0x2000usize);
+        memory.put(0x2048, 0x3000usize);
+        memory.put(0x3010, 1234u32);
+
+        let chain: PointerChain<u32, _> =
+            PointerChain::with_backend(memory.clone(), &[0x1000, 0x48, 0x10]);
+        assert_eq!(chain.eval(), Ok(0x3010 as *mut u32));
+        assert_eq!(chain.read(), Ok(1234));
+        assert_eq!(chain.write(4321), Ok(()));
+        assert_eq!(memory.get::<u32>(0x3010), Some(4321));
+
+        let broken: PointerChain<u32, _> =
+            PointerChain::with_backend(memory.clone(), &[0x1000, 0x50, 0x10]);
+        let error = PointerChainError::Deref { level: 1, address: 0x2050 };
+        assert_eq!(broken.eval(), Err(error));
+        assert_eq!(broken.read(), Err(error));
+        assert_eq!(broken.write(0), Err(error));
+
+        let read_only: PointerChain<u32, _> =
+            PointerChain::with_backend(memory.clone(), &[0x1000, 0x48, 0x20]);
+        assert_eq!(read_only.read(), Err(PointerChainError::Read { address: 0x3020 }));
+        assert_eq!(read_only.write(0), Err(PointerChainError::Write { address: 0x3020 }));
+
+        memory.put(0x3014, 5678u32);
+        assert_eq!(chain.field::<u32>(4).read(), Ok(5678));
+    }
+
+    #[test]
+    fn test_pointer_chain_syntax() {
+        let memory = FakeMemory::new();
+        memory.add_module("sekiro.exe", 0x1_4000_0000, 0x400_0000);
+
+        let chain: PointerChain<f32, _> = PointerChain::parse_with_backend(
+            memory.clone(),
+            "sekiro.exe+3B67DF0 -> 48 -> 28 -> 80",
+        )
+        .unwrap();
+        assert_eq!(chain.base, 0x1_43B6_7DF0);
+        assert_eq!(chain.offsets, vec![0x48, 0x28, 0x80]);
+        assert_eq!(chain.to_string(), "sekiro.exe+3B67DF0 -> 48 -> 28 -> 80");
+        assert_eq!(format!("{chain:?}"), "PointerChain(sekiro.exe+3B67DF0 -> 48 -> 28 -> 80)");
+
+        let chain: PointerChain<f32, _> =
+            PointerChain::parse_with_backend(memory.clone(), "SEKIRO.EXE + 0x10->0x8").unwrap();
+        assert_eq!(chain.to_string(), "sekiro.exe+10 -> 8");
+
+        let chain: PointerChain<f32, _> =
+            PointerChain::parse_with_backend(memory.clone(), "7FF600001000 -> 10").unwrap();
+        assert_eq!(chain.to_string(), "7FF600001000 -> 10");
+
+        let parse = |s| PointerChain::<f32, _>::parse_with_backend(memory.clone(), s).unwrap_err();
+        assert_eq!(parse(""), PointerChainParseError::Empty);
+        assert_eq!(parse("sekiro.exe+XYZ"), PointerChainParseError::InvalidNumber("XYZ".into()));
+        assert_eq!(
+            parse("ntdll.dll+10"),
+            PointerChainParseError::ModuleNotFound("ntdll.dll".into())
+        );
+    }
+
+    #[test]
+    fn test_code_patch() {
+        let memory = FakeMemory::new();
+        memory.map(0x1000, &[0x74, 0x30]);
+
+        let patch = CodePatch::with_backend(
+            memory.clone(),
+            "Test",
+            Version::V1_06_0,
+            0x1000,
+            &[0x74, 0x30],
+            &[0x75, 0x30],
+        );
+        assert_eq!(patch.verify(), Ok(()));
+        assert_eq!(patch.apply(), Ok(()));
+        assert!(patch.is_applied());
+        assert_eq!(memory.get::<[u8; 2]>(0x1000), Some([0x75, 0x30]));
+        assert_eq!(patch.revert(), Ok(()));
+        assert!(!patch.is_applied());
+        assert_eq!(memory.get::<[u8; 2]>(0x1000), Some([0x74, 0x30]));
+
+        memory.map(0x1000, &[0x90, 0x90]);
+        assert_eq!(patch.apply().unwrap_err().kind, CodePatchErrorKind::Mismatch {
+            expected: vec![0x74, 0x30],
+            current: vec![0x90, 0x90]
+        });
+        assert_eq!(memory.get::<[u8; 2]>(0x1000), Some([0x90, 0x90]));
+
+        // Already patched by someone else: left alone on revert.
+        memory.map(0x1000, &[0x75, 0x30]);
+        assert_eq!(patch.apply(), Ok(()));
+        assert!(!patch.is_applied());
+        assert_eq!(patch.revert(), Ok(()));
+        assert_eq!(memory.get::<[u8; 2]>(0x1000), Some([0x75, 0x30]));
+    }
+
+    #[test]
+    fn test_bitflag() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 0b0101u8);
+
+        let flag = bitflag!(memory => 0b10u8; 0x1000);
+        assert_eq!(flag.get(), Ok(false));
+        assert_eq!(flag.set(true), Ok(()));
+        assert_eq!(memory.get::<u8>(0x1000), Some(0b0111));
+        flag.toggle().unwrap();
+        assert_eq!(memory.get::<u8>(0x1000), Some(0b0101));
+
+        let unmapped = bitflag!(memory => 0b10u8; 0x2000);
+        assert_eq!(unmapped.get(), Err(PointerChainError::Read { address: 0x2000 }));
+        assert_eq!(unmapped.set(true), Err(PointerChainError::Read { address: 0x2000 }));
+    }
+}
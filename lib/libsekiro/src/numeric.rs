@@ -0,0 +1,175 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::backend::{CurrentProcess, MemoryBackend};
+use crate::freeze::Freezer;
+use crate::memedit::{PointerChain, PointerChainError};
+
+macro_rules! numeric_types {
+    ([ $( ($variant:ident, $ty:ty, $name:literal), )* ]) => {
+        /// Type of the datum pointed to by a [`NumericChain`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum ValueType {
+            $($variant,)*
+        }
+
+        impl FromStr for ValueType {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok(ValueType::$variant),)*
+                    e => Err(format!("\"{e}\" is not a valid value type")),
+                }
+            }
+        }
+
+        impl ValueType {
+            /// Converts `value` to this type and back, e.g. to compare it
+            /// with values read from a chain.
+            pub fn cast(&self, value: f64) -> f64 {
+                match self {
+                    $(ValueType::$variant => value as $ty as f64,)*
+                }
+            }
+        }
+
+        impl Display for ValueType {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(ValueType::$variant => write!(f, $name),)*
+                }
+            }
+        }
+
+        /// A pointer chain to a number whose type is only known at runtime,
+        /// e.g. because it was declared in a configuration file. Values are
+        /// read and written as `f64`.
+        #[derive(Debug, Clone)]
+        pub enum NumericChain<M: MemoryBackend = CurrentProcess> {
+            $($variant(PointerChain<$ty, M>),)*
+        }
+
+        $(
+            impl<M: MemoryBackend> From<PointerChain<$ty, M>> for NumericChain<M> {
+                fn from(chain: PointerChain<$ty, M>) -> Self {
+                    NumericChain::$variant(chain)
+                }
+            }
+        )*
+
+        impl<M: MemoryBackend> NumericChain<M> {
+            pub fn with_backend(backend: M, value_type: ValueType, chain: &[usize]) -> Self {
+                match value_type {
+                    $(ValueType::$variant => {
+                        NumericChain::$variant(PointerChain::with_backend(backend, chain))
+                    },)*
+                }
+            }
+
+            pub fn value_type(&self) -> ValueType {
+                match self {
+                    $(NumericChain::$variant(_) => ValueType::$variant,)*
+                }
+            }
+
+            pub fn read(&self) -> Result<f64, PointerChainError> {
+                match self {
+                    $(NumericChain::$variant(chain) => chain.read().map(|v| v as f64),)*
+                }
+            }
+
+            /// Writes `value`, converted with `as` to the type of the datum.
+            pub fn write(&self, value: f64) -> Result<(), PointerChainError> {
+                match self {
+                    $(NumericChain::$variant(chain) => chain.write(value as $ty),)*
+                }
+            }
+
+            /// Freezes the datum to `value`, or to its current value if
+            /// `value` is `None`.
+            pub fn freeze(
+                &self,
+                freezer: &Freezer,
+                label: &str,
+                value: Option<f64>,
+            ) -> Result<(), PointerChainError> {
+                match (self, value) {
+                    $(
+                        (NumericChain::$variant(chain), Some(value)) => {
+                            freezer.freeze(label, chain.clone(), value as $ty);
+                            Ok(())
+                        },
+                        (NumericChain::$variant(chain), None) => {
+                            freezer.freeze_current(label, chain.clone())
+                        },
+                    )*
+                }
+            }
+        }
+
+        impl<M: MemoryBackend> Display for NumericChain<M> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(NumericChain::$variant(chain) => write!(f, "{chain}"),)*
+                }
+            }
+        }
+    };
+}
+
+numeric_types!([
+    (U8, u8, "u8"),
+    (U16, u16, "u16"),
+    (U32, u32, "u32"),
+    (U64, u64, "u64"),
+    (I8, i8, "i8"),
+    (I16, i16, "i16"),
+    (I32, i32, "i32"),
+    (I64, i64, "i64"),
+    (F32, f32, "f32"),
+    (F64, f64, "f64"),
+]);
+
+impl ValueType {
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, ValueType::F32 | ValueType::F64)
+    }
+
+    /// Formats a value read from a chain of this type.
+    pub fn format(&self, value: f64) -> String {
+        if self.is_integer() {
+            format!("{value}")
+        } else {
+            format!("{value:.3}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FakeMemory;
+
+    #[test]
+    fn test_numeric_chain() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 300u16);
+
+        let chain = NumericChain::with_backend(memory.clone(), "u16".parse().unwrap(), &[0x1000]);
+        assert_eq!(chain.value_type(), ValueType::U16);
+        assert_eq!(chain.read(), Ok(300.));
+        assert_eq!(chain.write(1234.7), Ok(()));
+        assert_eq!(memory.get::<u16>(0x1000), Some(1234));
+
+        let freezer = Freezer::new();
+        chain.freeze(&freezer, "Value", None).unwrap();
+        memory.put(0x1000, 0u16);
+        freezer.tick();
+        assert_eq!(memory.get::<u16>(0x1000), Some(1234));
+
+        assert_eq!(ValueType::U8.cast(300.5), 255.);
+        assert_eq!(ValueType::F32.cast(0.1), 0.1f32 as f64);
+        assert!("u128".parse::<ValueType>().is_err());
+    }
+}
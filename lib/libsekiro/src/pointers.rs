@@ -3,57 +3,70 @@ use std::ptr::null_mut;
 use windows::core::PCSTR;
 use windows::Win32::System::LibraryLoader::GetModuleHandleA;
 
+use crate::backend::{CurrentProcess, MemoryBackend};
 use crate::codegen::base_addresses::BaseAddresses;
-use crate::memedit::{Bitflag, PointerChain};
-use crate::prelude::{Version, VERSION};
+use crate::memedit::{Bitflag, CodePatch, CodePatchError, PointerChain};
+use crate::numeric::{NumericChain, ValueType};
+use crate::scan::CURRENT_BASE_ADDRESSES;
+use crate::version::{Version, LATEST_VERSION, VERSION};
 use crate::{bitflag, pointer_chain};
 
-pub struct Pointers {
-    pub position: PointerChain<[f32; 4]>,
-    pub quitout: PointerChain<u8>,
-    pub show_cursor: Bitflag<u8>,
-    pub igt: PointerChain<u32>,
-
-    pub fps: PointerChain<f32>,
-
-    pub anim_speed: PointerChain<f32>,
-
-    pub render_world: Bitflag<u8>,
-    pub render_objects: Bitflag<u8>,
-    pub render_mobs: Bitflag<u8>,
-    pub render_effects: Bitflag<u8>,
-
-    pub debug_render0: Bitflag<u8>,
-    pub debug_render1: Bitflag<u8>,
-    pub debug_render2: Bitflag<u8>,
-    pub debug_render3: Bitflag<u8>,
-    pub debug_render4: Bitflag<u8>,
-    pub debug_render5: Bitflag<u8>,
-    pub debug_render6: Bitflag<u8>,
-    pub debug_render7: Bitflag<u8>,
-
-    pub debug_color: PointerChain<i32>,
-
-    pub debug_show: Bitflag<u8>,
-    pub grapple_debug_path: Bitflag<u8>,
-    pub grapple_debug_col: Bitflag<u8>,
-
-    pub player_no_goods_consume: Bitflag<u8>,
-    pub player_no_resource_item_consume: Bitflag<u8>,
-    pub player_no_revival_consume: Bitflag<u8>,
-    pub player_hide: Bitflag<u8>,
-    pub player_silence: Bitflag<u8>,
-    pub player_no_dead: Bitflag<u8>,
-    pub player_exterminate: Bitflag<u8>,
-    pub player_exterminate_stamina: Bitflag<u8>,
-
-    pub all_no_dead: Bitflag<u8>,
-    pub all_no_damage: Bitflag<u8>,
-    pub all_no_hit: Bitflag<u8>,
-    pub all_no_attack: Bitflag<u8>,
-    pub all_no_move: Bitflag<u8>,
-    pub all_no_update_ai: Bitflag<u8>,
-    pub all_no_stamina_consume: Bitflag<u8>,
+pub struct Pointers<M: MemoryBackend = CurrentProcess> {
+    pub position: PointerChain<[f32; 4], M>,
+    pub quitout: PointerChain<u8, M>,
+    pub show_cursor: Bitflag<u8, M>,
+    pub igt: PointerChain<u32, M>,
+
+    pub fps: PointerChain<f32, M>,
+
+    pub anim_speed: PointerChain<f32, M>,
+    /// Time scale of the whole game, unlike `anim_speed` which only affects
+    /// the player's animations.
+    pub game_speed: PointerChain<f32, M>,
+
+    /// Pointers into the characters, whose layout is only known for 1.05
+    /// and later. `None` on older versions.
+    pub chr: Option<ChrPointers<M>>,
+
+    pub render_world: Bitflag<u8, M>,
+    pub render_objects: Bitflag<u8, M>,
+    pub render_mobs: Bitflag<u8, M>,
+    pub render_effects: Bitflag<u8, M>,
+
+    pub debug_render0: Bitflag<u8, M>,
+    pub debug_render1: Bitflag<u8, M>,
+    pub debug_render2: Bitflag<u8, M>,
+    pub debug_render3: Bitflag<u8, M>,
+    pub debug_render4: Bitflag<u8, M>,
+    pub debug_render5: Bitflag<u8, M>,
+    pub debug_render6: Bitflag<u8, M>,
+    pub debug_render7: Bitflag<u8, M>,
+
+    pub debug_color: PointerChain<i32, M>,
+
+    pub debug_show: Bitflag<u8, M>,
+    pub grapple_debug_path: Bitflag<u8, M>,
+    pub grapple_debug_col: Bitflag<u8, M>,
+
+    pub player_no_goods_consume: Bitflag<u8, M>,
+    pub player_no_resource_item_consume: Bitflag<u8, M>,
+    pub player_no_revival_consume: Bitflag<u8, M>,
+    pub player_hide: Bitflag<u8, M>,
+    pub player_silence: Bitflag<u8, M>,
+    pub player_no_dead: Bitflag<u8, M>,
+    pub player_exterminate: Bitflag<u8, M>,
+    pub player_exterminate_stamina: Bitflag<u8, M>,
+
+    pub all_no_dead: Bitflag<u8, M>,
+    pub all_no_damage: Bitflag<u8, M>,
+    pub all_no_hit: Bitflag<u8, M>,
+    pub all_no_attack: Bitflag<u8, M>,
+    pub all_no_move: Bitflag<u8, M>,
+    pub all_no_update_ai: Bitflag<u8, M>,
+    pub all_no_stamina_consume: Bitflag<u8, M>,
+
+    backend: M,
+    base_addresses: BaseAddresses,
 }
 
 impl Default for Pointers {
@@ -62,13 +75,188 @@ impl Default for Pointers {
     }
 }
 
+pub(crate) fn base_module_address() -> usize {
+    unsafe { GetModuleHandleA(PCSTR(null_mut())).unwrap() }.0 as usize
+}
+
 impl Pointers {
+    /// Builds the pointers for the running game. The base addresses which
+    /// weren't generated for its version are found by scanning its code.
     pub fn new() -> Self {
-        let base_module_address =
-            unsafe { GetModuleHandleA(PCSTR(null_mut())).unwrap() }.0 as usize;
-        let base_addresses = BaseAddresses::from(*crate::version::VERSION)
-            .with_module_base_addr(base_module_address);
+        Self::with_backend(
+            CurrentProcess,
+            CURRENT_BASE_ADDRESSES.with_module_base_addr(base_module_address()),
+            VERSION.unwrap_or(LATEST_VERSION),
+        )
+    }
+}
+
+/// The patches to the game's code applied by the tools.
+pub struct CodePatches<M: MemoryBackend = CurrentProcess> {
+    pub no_logo: CodePatch<M>,
+    pub font_patch: CodePatch<M>,
+}
+
+impl Default for CodePatches {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
+impl CodePatches {
+    pub fn new() -> Self {
+        Self::with_backend(
+            CurrentProcess,
+            base_module_address(),
+            CURRENT_BASE_ADDRESSES.base_addresses(),
+            VERSION.unwrap_or(LATEST_VERSION),
+        )
+    }
+}
+
+impl<M: MemoryBackend> CodePatches<M> {
+    /// `base_addresses` must not be relocated: the patch offsets are relative
+    /// to `module_base`. Offsets left at 0 by a failed scan yield patches
+    /// which fail to apply.
+    pub fn with_backend(
+        backend: M,
+        module_base: usize,
+        base_addresses: &BaseAddresses,
+        version: Version,
+    ) -> Self {
+        CodePatches {
+            no_logo: CodePatch::with_backend(
+                backend.clone(),
+                "No logo",
+                version,
+                relocate(module_base, base_addresses.no_logo, 0),
+                &[0x74, 0x30],
+                &[0x75, 0x30],
+            ),
+            // Credits to ElaDiDu for the font crash fix
+            font_patch: CodePatch::with_backend(
+                backend,
+                "Font",
+                version,
+                relocate(module_base, base_addresses.font_patch, -0x24),
+                &[0x48],
+                &[0xC3],
+            ),
+        }
+    }
+
+    /// Reverts all the patches that were applied, collecting the errors of
+    /// the ones that couldn't be.
+    pub fn revert_all(&self) -> Vec<CodePatchError> {
+        [&self.no_logo, &self.font_patch].into_iter().filter_map(|p| p.revert().err()).collect()
+    }
+}
+
+/// Pointers into the player's `PlayerIns` and the `ChrIns` it is locked
+/// on to.
+#[derive(Debug, Clone)]
+pub struct ChrPointers<M: MemoryBackend = CurrentProcess> {
+    pub hp: PointerChain<i32, M>,
+    pub max_hp: PointerChain<i32, M>,
+    pub posture: PointerChain<i32, M>,
+    pub max_posture: PointerChain<i32, M>,
+    /// Resurrective power nodes currently available.
+    pub resurrections: PointerChain<i32, M>,
+
+    pub target_hp: PointerChain<i32, M>,
+    pub target_max_hp: PointerChain<i32, M>,
+    pub target_posture: PointerChain<i32, M>,
+    pub target_max_posture: PointerChain<i32, M>,
+    /// Seconds left before the target's posture starts regenerating.
+    pub target_posture_regen_delay: PointerChain<f32, M>,
+    pub target_entity_id: PointerChain<u32, M>,
+    pub target_param_id: PointerChain<i32, M>,
+
+    /// Lets the player go through walls and floors.
+    pub no_collision: Bitflag<u8, M>,
+    /// Keeps the player from falling, e.g. while flying with no collision.
+    pub no_gravity: Bitflag<u8, M>,
+}
+
+impl<M: MemoryBackend> ChrPointers<M> {
+    /// `player_position` is the relocated base address of the same name.
+    pub fn with_backend(backend: M, player_position: usize) -> Self {
+        ChrPointers {
+            // PlayerIns -> ChrModules -> ChrDataModule
+            hp: pointer_chain!(backend => player_position, 0x48, 0x1FF8, 0x18, 0x130),
+            max_hp: pointer_chain!(backend => player_position, 0x48, 0x1FF8, 0x18, 0x134),
+            posture: pointer_chain!(backend => player_position, 0x48, 0x1FF8, 0x18, 0x148),
+            max_posture: pointer_chain!(backend => player_position, 0x48, 0x1FF8, 0x18, 0x14C),
+            // PlayerIns -> PlayerGameData
+            resurrections: pointer_chain!(backend => player_position, 0x48, 0x1FA0, 0x7C),
+
+            // PlayerIns -> LockOnModule -> locked-on ChrIns, then as above
+            target_hp: pointer_chain!(backend => player_position, 0x48, 0x1F90, 0x68, 0x1FF8, 0x18, 0x130),
+            target_max_hp: pointer_chain!(backend => player_position, 0x48, 0x1F90, 0x68, 0x1FF8, 0x18, 0x134),
+            target_posture: pointer_chain!(backend => player_position, 0x48, 0x1F90, 0x68, 0x1FF8, 0x18, 0x148),
+            target_max_posture: pointer_chain!(backend => player_position, 0x48, 0x1F90, 0x68, 0x1FF8, 0x18, 0x14C),
+            target_posture_regen_delay: pointer_chain!(backend => player_position, 0x48, 0x1F90, 0x68, 0x1FF8, 0x18, 0x16C),
+            target_entity_id: pointer_chain!(backend => player_position, 0x48, 0x1F90, 0x68, 0x8),
+            target_param_id: pointer_chain!(backend => player_position, 0x48, 0x1F90, 0x68, 0x60),
+
+            // PlayerIns -> ChrModules -> ChrPhysicsModule
+            no_collision: bitflag!(backend => 0b1000; player_position, 0x48, 0x1FF8, 0x68, 0x1D3),
+            // PlayerIns -> ChrFlags
+            no_gravity: bitflag!(backend => 0b1000000; player_position, 0x48, 0x1A08),
+        }
+    }
+}
+
+/// Pointers to the cameras, built on the static pointer to `FieldArea`.
+#[derive(Debug, Clone)]
+pub struct CameraPointers<M: MemoryBackend = CurrentProcess> {
+    /// Whether the free camera is detached from the player.
+    pub free_cam: Bitflag<u8, M>,
+    /// Matrix of the free camera, as rows: right, up and forward vectors,
+    /// then position.
+    pub free_cam_matrix: PointerChain<[f32; 16], M>,
+    /// Matrix of the camera following the player, laid out as above.
+    pub player_cam_matrix: PointerChain<[f32; 16], M>,
+}
+
+impl Default for CameraPointers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CameraPointers {
+    pub fn new() -> Self {
+        let base_addresses = CURRENT_BASE_ADDRESSES.with_module_base_addr(base_module_address());
+        Self::with_backend(CurrentProcess, base_addresses.field_area)
+    }
+}
+
+impl<M: MemoryBackend> CameraPointers<M> {
+    /// `field_area` is the absolute address of the static pointer to
+    /// `FieldArea`, or 0 if it couldn't be found.
+    pub fn with_backend(backend: M, field_area: usize) -> Self {
+        CameraPointers {
+            // FieldArea -> GameRend
+            free_cam: bitflag!(backend => 0b1; field_area, 0x18, 0xE0),
+            free_cam_matrix: pointer_chain!(backend => field_area, 0x18, 0xE8, 0x10),
+            player_cam_matrix: pointer_chain!(backend => field_area, 0x18, 0x30, 0x10),
+        }
+    }
+}
+
+fn relocate(module_base: usize, offset: usize, delta: isize) -> usize {
+    if offset == 0 {
+        0
+    } else {
+        (module_base + offset).saturating_add_signed(delta)
+    }
+}
+
+impl<M: MemoryBackend> Pointers<M> {
+    /// Builds the pointers for the given game version out of absolute base
+    /// addresses, evaluating them against `backend`.
+    pub fn with_backend(backend: M, base_addresses: BaseAddresses, version: Version) -> Self {
         let BaseAddresses {
             quitout,
             render_world,
@@ -81,38 +269,46 @@ impl Pointers {
             grapple_debug,
             fps,
             ..
-        } = base_addresses;
+        } = base_addresses.clone();
 
-        let offs_player_no_dead: isize = match *VERSION {
+        let offs_player_no_dead: isize = match version {
             Version::V1_02_0 | Version::V1_03_0 | Version::V1_04_0 => 33,
             Version::V1_05_0 | Version::V1_06_0 => -3,
         };
 
-        let offs_player_exterminate: isize = match *VERSION {
+        let offs_player_exterminate: isize = match version {
             Version::V1_02_0 | Version::V1_03_0 | Version::V1_04_0 => 52,
             Version::V1_05_0 | Version::V1_06_0 => -2,
         };
 
-        let offs_player_exterminate_stamina: isize = match *VERSION {
+        let offs_player_exterminate_stamina: isize = match version {
             Version::V1_02_0 | Version::V1_03_0 | Version::V1_04_0 => -1,
             Version::V1_05_0 | Version::V1_06_0 => -1,
         };
 
-        let offs_grapple_debug: usize = match *VERSION {
+        let chr = match version {
+            Version::V1_02_0 | Version::V1_03_0 | Version::V1_04_0 => None,
+            Version::V1_05_0 | Version::V1_06_0 => {
+                Some(ChrPointers::with_backend(backend.clone(), player_position))
+            },
+        };
+
+        let offs_grapple_debug: usize = match version {
             Version::V1_02_0 | Version::V1_03_0 | Version::V1_04_0 => 0xEC8,
             Version::V1_05_0 | Version::V1_06_0 => 0xF68,
         };
 
         Pointers {
-            position: pointer_chain!(player_position, 0x48, 0x28, 0x80),
-            quitout: pointer_chain!(quitout, 0x23C),
-            show_cursor: bitflag!(0b1; show_cursor),
-            igt: pointer_chain!(igt, 0x9C),
+            position: pointer_chain!(backend => player_position, 0x48, 0x28, 0x80),
+            quitout: pointer_chain!(backend => quitout, 0x23C),
+            show_cursor: bitflag!(backend => 0b1; show_cursor),
+            igt: pointer_chain!(backend => igt, 0x9C),
 
-            fps: pointer_chain!(fps, 0x2BC),
+            fps: pointer_chain!(backend => fps, 0x2BC),
+            game_speed: pointer_chain!(backend => fps, 0x344),
 
             anim_speed: pointer_chain!(
-                player_position,
+                backend => player_position,
                 0x48,
                 0x28,
                 0xA40,
@@ -122,40 +318,111 @@ impl Pointers {
                 0xD00
             ),
 
-            render_world: bitflag!(0b1; render_world),
-            render_objects: bitflag!(0b1; render_world+1),
-            render_mobs: bitflag!(0b1; render_world+2),
-            render_effects: bitflag!(0b1; render_world+3),
-            debug_render0: bitflag!(0b1; debug_render),
-            debug_render1: bitflag!(0b1; debug_render + 1),
-            debug_render2: bitflag!(0b1; debug_render + 2),
-            debug_render3: bitflag!(0b1; debug_render + 5),
-            debug_render4: bitflag!(0b1; debug_render + 6),
-            debug_render5: bitflag!(0b1; debug_render + 7),
-            debug_render6: bitflag!(0b1; debug_render + 8),
-            debug_render7: bitflag!(0b1; debug_render + 9),
-
-            debug_color: pointer_chain!(debug_render + 0xC),
-
-            debug_show: bitflag!(0b1; debug_show, 0x6F),
-            grapple_debug_path: bitflag!(0b1; grapple_debug, 0xC8, 0x20, offs_grapple_debug),
-            grapple_debug_col: bitflag!(0b1; grapple_debug, 0xC8, 0x20, offs_grapple_debug + 0x2),
-
-            player_no_goods_consume: bitflag!(0b1; debug_flags),
-            player_no_resource_item_consume: bitflag!(0b1; debug_flags + 1),
-            player_no_revival_consume: bitflag!(0b1; debug_flags + 2),
-            player_hide: bitflag!(0b1; debug_flags + 6),
-            player_silence: bitflag!(0b1; debug_flags + 7),
-            player_no_dead: bitflag!(0b1; debug_flags.saturating_add_signed(offs_player_no_dead)),
-            player_exterminate: bitflag!(0b1; debug_flags.saturating_add_signed(offs_player_exterminate)),
-            player_exterminate_stamina: bitflag!(0b1; debug_flags.saturating_add_signed(offs_player_exterminate_stamina)),
-            all_no_dead: bitflag!(0b1; debug_flags + 8),
-            all_no_damage: bitflag!(0b1; debug_flags + 9),
-            all_no_hit: bitflag!(0b1; debug_flags + 10),
-            all_no_attack: bitflag!(0b1; debug_flags + 11),
-            all_no_move: bitflag!(0b1; debug_flags + 12),
-            all_no_update_ai: bitflag!(0b1; debug_flags + 13),
-            all_no_stamina_consume: bitflag!(0b1; debug_flags + 20),
+            chr,
+
+            render_world: bitflag!(backend => 0b1; render_world),
+            render_objects: bitflag!(backend => 0b1; render_world+1),
+            render_mobs: bitflag!(backend => 0b1; render_world+2),
+            render_effects: bitflag!(backend => 0b1; render_world+3),
+            debug_render0: bitflag!(backend => 0b1; debug_render),
+            debug_render1: bitflag!(backend => 0b1; debug_render + 1),
+            debug_render2: bitflag!(backend => 0b1; debug_render + 2),
+            debug_render3: bitflag!(backend => 0b1; debug_render + 5),
+            debug_render4: bitflag!(backend => 0b1; debug_render + 6),
+            debug_render5: bitflag!(backend => 0b1; debug_render + 7),
+            debug_render6: bitflag!(backend => 0b1; debug_render + 8),
+            debug_render7: bitflag!(backend => 0b1; debug_render + 9),
+
+            debug_color: pointer_chain!(backend => debug_render + 0xC),
+
+            debug_show: bitflag!(backend => 0b1; debug_show, 0x6F),
+            grapple_debug_path: bitflag!(backend => 0b1; grapple_debug, 0xC8, 0x20, offs_grapple_debug),
+            grapple_debug_col: bitflag!(backend => 0b1; grapple_debug, 0xC8, 0x20, offs_grapple_debug + 0x2),
+
+            player_no_goods_consume: bitflag!(backend => 0b1; debug_flags),
+            player_no_resource_item_consume: bitflag!(backend => 0b1; debug_flags + 1),
+            player_no_revival_consume: bitflag!(backend => 0b1; debug_flags + 2),
+            player_hide: bitflag!(backend => 0b1; debug_flags + 6),
+            player_silence: bitflag!(backend => 0b1; debug_flags + 7),
+            player_no_dead: bitflag!(backend => 0b1; debug_flags.saturating_add_signed(offs_player_no_dead)),
+            player_exterminate: bitflag!(backend => 0b1; debug_flags.saturating_add_signed(offs_player_exterminate)),
+            player_exterminate_stamina: bitflag!(backend => 0b1; debug_flags.saturating_add_signed(offs_player_exterminate_stamina)),
+            all_no_dead: bitflag!(backend => 0b1; debug_flags + 8),
+            all_no_damage: bitflag!(backend => 0b1; debug_flags + 9),
+            all_no_hit: bitflag!(backend => 0b1; debug_flags + 10),
+            all_no_attack: bitflag!(backend => 0b1; debug_flags + 11),
+            all_no_move: bitflag!(backend => 0b1; debug_flags + 12),
+            all_no_update_ai: bitflag!(backend => 0b1; debug_flags + 13),
+            all_no_stamina_consume: bitflag!(backend => 0b1; debug_flags + 20),
+
+            backend,
+            base_addresses,
         }
     }
+
+    /// Builds a chain starting at the base address named `base` (see
+    /// [`BaseAddresses::get`]), followed by `offsets`.
+    pub fn chain<T>(&self, base: &str, offsets: &[usize]) -> Option<PointerChain<T, M>> {
+        let chain: Vec<usize> = std::iter::once(self.base_addresses.get(base)?)
+            .chain(offsets.iter().copied())
+            .collect();
+        Some(PointerChain::with_backend(self.backend.clone(), &chain))
+    }
+
+    /// Like [`Pointers::chain`], for a datum whose type is only known at
+    /// runtime.
+    pub fn numeric_chain(
+        &self,
+        base: &str,
+        offsets: &[usize],
+        value_type: ValueType,
+    ) -> Option<NumericChain<M>> {
+        let chain: Vec<usize> = std::iter::once(self.base_addresses.get(base)?)
+            .chain(offsets.iter().copied())
+            .collect();
+        Some(NumericChain::with_backend(self.backend.clone(), value_type, &chain))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FakeMemory;
+    use crate::codegen::base_addresses::{BASE_ADDRESSES_1_04_0, BASE_ADDRESSES_1_06_0};
+    use crate::memedit::PointerChainError;
+
+    #[test]
+    fn test_fake_pointers() {
+        let memory = FakeMemory::new();
+        let module_base = 0x1_4000_0000;
+        let base_addresses = BASE_ADDRESSES_1_06_0.with_module_base_addr(module_base);
+
+        memory.put(base_addresses.player_position, 0x1000usize);
+        memory.put(0x1048, 0x2000usize);
+        memory.put(0x2028, 0x3000usize);
+        memory.put(0x3080, [1f32, 2., 3., 4.]);
+        memory.put(0x2000 + 0x1FF8, 0x4000usize);
+        memory.put(0x4018, 0x5000usize);
+        memory.put(0x5130, [850i32, 900]);
+        let debug_flags = base_addresses.debug_flags;
+        memory.put(debug_flags + 8, 0u8);
+
+        let pointers = Pointers::with_backend(memory.clone(), base_addresses, Version::V1_06_0);
+
+        assert_eq!(pointers.position.read(), Ok([1., 2., 3., 4.]));
+        let chr = pointers.chr.as_ref().unwrap();
+        assert_eq!(chr.hp.read(), Ok(850));
+        assert_eq!(chr.max_hp.read(), Ok(900));
+        assert!(matches!(pointers.igt.read(), Err(PointerChainError::Deref { level: 0, .. })));
+
+        assert_eq!(pointers.all_no_dead.set(true), Ok(()));
+        assert_eq!(pointers.all_no_dead.get(), Ok(true));
+        assert_eq!(memory.get::<u8>(debug_flags + 8), Some(1));
+
+        let base_addresses = BASE_ADDRESSES_1_04_0.with_module_base_addr(module_base);
+        memory.put(base_addresses.player_position, 0x1000usize);
+        let pointers = Pointers::with_backend(memory.clone(), base_addresses, Version::V1_04_0);
+        assert_eq!(pointers.position.read(), Ok([1., 2., 3., 4.]));
+        assert!(pointers.chr.is_none());
+    }
 }
@@ -0,0 +1,231 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use widestring::{U16CStr, U16CString};
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::System::Diagnostics::Debug::{ReadProcessMemory, WriteProcessMemory};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Module32FirstW, Module32NextW, Process32FirstW, Process32NextW,
+    MODULEENTRY32W, PROCESSENTRY32W, TH32CS_SNAPMODULE, TH32CS_SNAPMODULE32, TH32CS_SNAPPROCESS,
+};
+use windows::Win32::System::Threading::{
+    OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_OPERATION, PROCESS_VM_READ,
+    PROCESS_VM_WRITE,
+};
+
+use crate::backend::MemoryBackend;
+use crate::codegen::base_addresses::BaseAddresses;
+use crate::pointers::Pointers;
+use crate::scan::ScanResult;
+use crate::version::{get_file_version, Version, LATEST_VERSION};
+
+#[derive(Debug)]
+pub enum RemoteProcessError {
+    ProcessNotFound(String),
+    ModuleNotFound(String),
+    OpenProcess(windows::core::Error),
+    UnknownVersion(Option<(u32, u32, u32)>),
+}
+
+impl Display for RemoteProcessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteProcessError::ProcessNotFound(name) => write!(f, "Process {name} not found"),
+            RemoteProcessError::ModuleNotFound(name) => write!(f, "Module {name} not found"),
+            RemoteProcessError::OpenProcess(e) => write!(f, "Couldn't open process: {e}"),
+            RemoteProcessError::UnknownVersion(Some((maj, min, patch))) => {
+                write!(f, "Unrecognized version {maj}.{min:02}.{patch}")
+            },
+            RemoteProcessError::UnknownVersion(None) => write!(f, "Couldn't read game version"),
+        }
+    }
+}
+
+impl std::error::Error for RemoteProcessError {}
+
+struct OwnedHandle(HANDLE);
+
+unsafe impl Send for OwnedHandle {}
+unsafe impl Sync for OwnedHandle {}
+
+impl Drop for OwnedHandle {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0).ok() };
+    }
+}
+
+/// The address space of another process, e.g. a running `sekiro.exe` that
+/// an external tool wants to inspect without injecting anything.
+///
+/// Clones share the same process handle, which is closed when the last clone
+/// is dropped.
+#[derive(Clone)]
+pub struct RemoteProcess {
+    handle: Arc<OwnedHandle>,
+    pid: u32,
+    module_base: usize,
+    module_size: usize,
+    exe_path: U16CString,
+}
+
+impl RemoteProcess {
+    /// Opens the first running process whose executable is named `name`.
+    pub fn open_by_name(name: &str) -> Result<Self, RemoteProcessError> {
+        let pid = find_process(name)
+            .ok_or_else(|| RemoteProcessError::ProcessNotFound(name.to_string()))?;
+        Self::open(pid, name)
+    }
+
+    /// Opens the process with the given pid, whose main module is named
+    /// `module`.
+    pub fn open(pid: u32, module: &str) -> Result<Self, RemoteProcessError> {
+        let (module_base, module_size, exe_path) = find_module(pid, module)
+            .ok_or_else(|| RemoteProcessError::ModuleNotFound(module.to_string()))?;
+
+        let handle = unsafe {
+            OpenProcess(
+                PROCESS_VM_READ
+                    | PROCESS_VM_WRITE
+                    | PROCESS_VM_OPERATION
+                    | PROCESS_QUERY_LIMITED_INFORMATION,
+                false,
+                pid,
+            )
+        }
+        .map_err(RemoteProcessError::OpenProcess)?;
+
+        Ok(RemoteProcess {
+            handle: Arc::new(OwnedHandle(handle)),
+            pid,
+            module_base,
+            module_size,
+            exe_path,
+        })
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Base address of the main module in the process' address space.
+    pub fn module_base(&self) -> usize {
+        self.module_base
+    }
+
+    /// Size of the image of the main module.
+    pub fn module_size(&self) -> usize {
+        self.module_size
+    }
+
+    pub fn exe_path(&self) -> PathBuf {
+        PathBuf::from(self.exe_path.to_string_lossy())
+    }
+
+    /// Detects the game version from the executable of the process.
+    pub fn version(&self) -> Result<Version, RemoteProcessError> {
+        let file_version = get_file_version(&self.exe_path);
+        file_version
+            .and_then(|v| Version::try_from(v).ok())
+            .ok_or(RemoteProcessError::UnknownVersion(file_version))
+    }
+}
+
+impl MemoryBackend for RemoteProcess {
+    fn read_bytes(&self, addr: usize, buf: &mut [u8]) -> bool {
+        unsafe {
+            ReadProcessMemory(self.handle.0, addr as _, buf.as_mut_ptr() as _, buf.len(), None)
+                .is_ok()
+        }
+    }
+
+    fn write_bytes(&self, addr: usize, buf: &[u8]) -> bool {
+        unsafe {
+            WriteProcessMemory(self.handle.0, addr as _, buf.as_ptr() as _, buf.len(), None).is_ok()
+        }
+    }
+
+    // Only the main module is tracked.
+    fn module_base(&self, name: &str) -> Option<usize> {
+        self.exe_path()
+            .file_name()
+            .filter(|exe| exe.to_string_lossy().eq_ignore_ascii_case(name))
+            .map(|_| self.module_base)
+    }
+
+    fn module_at(&self, addr: usize) -> Option<(String, usize)> {
+        if !(self.module_base..self.module_base + self.module_size).contains(&addr) {
+            return None;
+        }
+        let exe_path = self.exe_path();
+        Some((exe_path.file_name()?.to_string_lossy().into_owned(), self.module_base))
+    }
+}
+
+impl Pointers<RemoteProcess> {
+    /// Builds the pointers for the game running in another process, detecting
+    /// its version from the executable. The base addresses which weren't
+    /// generated for its version are found by scanning the code of the
+    /// process.
+    pub fn for_process(process: RemoteProcess) -> Result<Self, RemoteProcessError> {
+        let (generated, version) = match process.version() {
+            Ok(version) => (BaseAddresses::from(version), version),
+            Err(RemoteProcessError::UnknownVersion(Some(_))) => {
+                (BaseAddresses::default(), LATEST_VERSION)
+            },
+            Err(e) => return Err(e),
+        };
+        let scan = ScanResult::scan_module(
+            &process,
+            generated,
+            process.module_base(),
+            process.module_size(),
+        );
+        let base_addresses = scan.with_module_base_addr(process.module_base());
+        Ok(Self::with_backend(process, base_addresses, version))
+    }
+}
+
+fn name_matches(name: &[u16], expected: &str) -> bool {
+    U16CStr::from_slice_truncate(name)
+        .map(|name| name.to_string_lossy().eq_ignore_ascii_case(expected))
+        .unwrap_or(false)
+}
+
+fn find_process(name: &str) -> Option<u32> {
+    let snapshot = OwnedHandle(unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }.ok()?);
+    let mut entry = PROCESSENTRY32W {
+        dwSize: std::mem::size_of::<PROCESSENTRY32W>() as _,
+        ..Default::default()
+    };
+
+    unsafe { Process32FirstW(snapshot.0, &mut entry) }.ok()?;
+
+    loop {
+        if name_matches(&entry.szExeFile, name) {
+            return Some(entry.th32ProcessID);
+        }
+        unsafe { Process32NextW(snapshot.0, &mut entry) }.ok()?;
+    }
+}
+
+fn find_module(pid: u32, name: &str) -> Option<(usize, usize, U16CString)> {
+    let snapshot = OwnedHandle(
+        unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPMODULE | TH32CS_SNAPMODULE32, pid) }.ok()?,
+    );
+    let mut entry =
+        MODULEENTRY32W { dwSize: std::mem::size_of::<MODULEENTRY32W>() as _, ..Default::default() };
+
+    unsafe { Module32FirstW(snapshot.0, &mut entry) }.ok()?;
+
+    loop {
+        if name_matches(&entry.szModule, name) {
+            return Some((
+                entry.modBaseAddr as usize,
+                entry.modBaseSize as usize,
+                U16CString::from_vec_truncate(entry.szExePath.to_vec()),
+            ));
+        }
+        unsafe { Module32NextW(snapshot.0, &mut entry) }.ok()?;
+    }
+}
@@ -0,0 +1,221 @@
+use std::ptr::null_mut;
+
+use lazy_static::lazy_static;
+use tracing::{info, warn};
+use windows::core::PCSTR;
+use windows::Win32::System::LibraryLoader::GetModuleHandleA;
+use windows::Win32::System::ProcessStatus::{GetModuleInformation, MODULEINFO};
+use windows::Win32::System::Threading::GetCurrentProcess;
+
+use crate::aobs::{Aob, AobKind, AOBS};
+use crate::backend::{CurrentProcess, MemoryBackend};
+use crate::codegen::base_addresses::BaseAddresses;
+use crate::version::VERSION;
+
+lazy_static! {
+    /// Module-relative base addresses of the current process: the generated
+    /// ones for its version, with the ones missing from there found by
+    /// scanning its main module. Scanning takes a while, so the module is
+    /// only read if some address wasn't generated, e.g. when the game
+    /// version is unknown.
+    pub static ref CURRENT_BASE_ADDRESSES: ScanResult = ScanResult::complete(
+        VERSION.map(BaseAddresses::from).unwrap_or_default(),
+        read_current_module,
+    );
+}
+
+/// Reads the image of the main module of the current process.
+fn read_current_module() -> Vec<u8> {
+    let module = unsafe { GetModuleHandleA(PCSTR(null_mut())).unwrap() };
+    let mut module_info = MODULEINFO::default();
+    unsafe {
+        GetModuleInformation(
+            GetCurrentProcess(),
+            module,
+            &mut module_info,
+            std::mem::size_of::<MODULEINFO>() as u32,
+        )
+    }
+    .ok();
+    read_module(&CurrentProcess, module_info.lpBaseOfDll as usize, module_info.SizeOfImage as usize)
+}
+
+/// Reads the image of the module at `module_base` through `backend`.
+/// Unreadable pages are read as zeroes.
+fn read_module<M: MemoryBackend>(backend: &M, module_base: usize, size: usize) -> Vec<u8> {
+    const PAGE_SIZE: usize = 0x1000;
+
+    let mut module = vec![0u8; size];
+    for (i, page) in module.chunks_mut(PAGE_SIZE).enumerate() {
+        if !backend.read_bytes(module_base + i * PAGE_SIZE, page) {
+            page.fill(0);
+        }
+    }
+    module
+}
+
+fn into_needle(pattern: &str) -> Vec<Option<u8>> {
+    pattern
+        .split_whitespace()
+        .map(|byte| match byte {
+            "?" | "??" => None,
+            x => u8::from_str_radix(x, 16).ok(),
+        })
+        .collect()
+}
+
+fn naive_search(bytes: &[u8], pattern: &[Option<u8>]) -> Option<usize> {
+    bytes.windows(pattern.len()).position(|wnd| {
+        wnd.iter().zip(pattern.iter()).all(|(byte, pattern)| match pattern {
+            Some(x) => byte == x,
+            None => true,
+        })
+    })
+}
+
+/// Finds the first pattern and resolves the RIP-relative address at
+/// `offset` from it, relative to the instruction ending at `deref_offset`.
+fn find_indirect(
+    bytes: &[u8],
+    patterns: &[&str],
+    offset: usize,
+    deref_offset: usize,
+) -> Option<usize> {
+    let base = patterns.iter().find_map(|p| naive_search(bytes, &into_needle(p)))?;
+    let rel = bytes.get(base + offset..base + offset + 4)?;
+    let rel = i32::from_le_bytes(rel.try_into().unwrap());
+    (base + deref_offset).checked_add_signed(rel as isize)
+}
+
+/// Returns the module-relative address the pattern resolves to.
+fn find(aob: &Aob, bytes: &[u8]) -> Option<usize> {
+    match aob.kind {
+        AobKind::Indirect { offset, deref_offset } => {
+            find_indirect(bytes, aob.patterns, offset, deref_offset)
+        },
+        AobKind::Direct => aob.patterns.iter().find_map(|p| naive_search(bytes, &into_needle(p))),
+    }
+}
+
+/// Base addresses found by scanning the game's code for the patterns used
+/// by the codegen, for game versions whose addresses weren't generated.
+///
+/// Addresses whose pattern didn't match are left at 0, so that pointer
+/// chains and patches built on top of them fail to evaluate instead of
+/// pointing at random memory: only the features relying on them are
+/// disabled.
+pub struct ScanResult {
+    base_addresses: BaseAddresses,
+    missing: Vec<&'static str>,
+}
+
+impl ScanResult {
+    /// Scans the bytes of a module image.
+    pub fn scan(module: &[u8]) -> Self {
+        Self::complete(BaseAddresses::default(), || module.to_vec())
+    }
+
+    /// Scans for the addresses left at 0 in `base_addresses`, reading the
+    /// module image only if there is any.
+    pub fn complete(mut base_addresses: BaseAddresses, module: impl FnOnce() -> Vec<u8>) -> Self {
+        let pending = AOBS
+            .iter()
+            .filter(|aob| base_addresses.get(&aob.symbol()) == Some(0))
+            .collect::<Vec<_>>();
+        let mut missing = Vec::new();
+        if pending.is_empty() {
+            return ScanResult { base_addresses, missing };
+        }
+
+        let module = module();
+        for aob in pending {
+            match find(aob, &module) {
+                Some(addr) => {
+                    info!("{}: found at {addr:#x}", aob.name);
+                    *base_addresses.get_mut(&aob.symbol()).unwrap() = addr;
+                },
+                None => {
+                    warn!("{}: pattern not found, disabling the features relying on it", aob.name);
+                    missing.push(aob.name);
+                },
+            }
+        }
+
+        ScanResult { base_addresses, missing }
+    }
+
+    /// Reads the image of the module at `module_base` through `backend`
+    /// and scans it for the addresses left at 0 in `base_addresses`.
+    pub fn scan_module<M: MemoryBackend>(
+        backend: &M,
+        base_addresses: BaseAddresses,
+        module_base: usize,
+        size: usize,
+    ) -> Self {
+        Self::complete(base_addresses, || read_module(backend, module_base, size))
+    }
+
+    /// Names of the patterns which didn't match.
+    pub fn missing(&self) -> &[&'static str] {
+        &self.missing
+    }
+
+    /// Module-relative addresses, like the generated ones.
+    pub fn base_addresses(&self) -> &BaseAddresses {
+        &self.base_addresses
+    }
+
+    /// Relocates the addresses like [`BaseAddresses::with_module_base_addr`],
+    /// leaving the missing ones at 0.
+    pub fn with_module_base_addr(&self, base: usize) -> BaseAddresses {
+        let mut base_addresses = self.base_addresses.clone();
+        for aob in AOBS.iter().filter(|aob| !self.missing.contains(&aob.name)) {
+            if let AobKind::Indirect { .. } = aob.kind {
+                *base_addresses.get_mut(&aob.symbol()).unwrap() += base;
+            }
+        }
+        base_addresses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan() {
+        // `mov rax, [rip + 0x100]` at 0x10, as in the `Quitout` pattern.
+        let mut module = vec![0xCCu8; 0x200];
+        module[0x10..0x1F].copy_from_slice(&[
+            0x48, 0x8B, 0x05, 0x00, 0x01, 0x00, 0x00, 0x48, 0x63, 0xC9, 0x89, 0x54, 0x88, 0x20,
+            0xC3,
+        ]);
+        module[0x80..0x89].copy_from_slice(&[0x48, 0x8b, 0xfa, 0x49, 0x8b, 0xf0, 0x48, 0x8b, 0xd9]);
+
+        let scan = ScanResult::scan(&module);
+        assert_eq!(scan.base_addresses().quitout, 0x117);
+        assert_eq!(scan.base_addresses().font_patch, 0x80);
+        assert!(!scan.missing().contains(&"Quitout"));
+        assert!(scan.missing().contains(&"Igt"));
+
+        assert!(scan.missing().contains(&"EventFlagMan"));
+
+        let relocated = scan.with_module_base_addr(0x1_4000_0000);
+        assert_eq!(relocated.quitout, 0x1_4000_0117);
+        assert_eq!(relocated.font_patch, 0x80);
+        assert_eq!(relocated.igt, 0);
+
+        // Generated addresses are kept, only the others are scanned for.
+        let generated = BaseAddresses { quitout: 0x42, ..Default::default() };
+        let scan = ScanResult::complete(generated, || module.clone());
+        assert_eq!(scan.base_addresses().quitout, 0x42);
+        assert_eq!(scan.base_addresses().font_patch, 0x80);
+    }
+
+    #[test]
+    fn test_aob_symbols() {
+        for aob in AOBS {
+            assert!(BaseAddresses::SYMBOLS.contains(&aob.symbol().as_str()), "{}", aob.name);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::backend::{CurrentProcess, MemoryBackend};
+use crate::memedit::{Bitflag, PointerChain, PointerChainError};
+use crate::numeric::NumericChain;
+
+/// State captured by a [`Snapshot`], in the order its chains were given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotState {
+    pub position: Option<[f32; 4]>,
+    pub values: Vec<f64>,
+    pub flags: Vec<bool>,
+}
+
+/// Captures a set of values and flags, and restores them all at once.
+///
+/// Restoring only writes once every chain could be read, so that a snapshot
+/// is either restored completely or not at all, e.g. during a loading
+/// screen. The position is written last, after the flags and values.
+#[derive(Debug, Clone)]
+pub struct Snapshot<M: MemoryBackend = CurrentProcess> {
+    position: Option<PointerChain<[f32; 4], M>>,
+    values: Vec<NumericChain<M>>,
+    flags: Vec<Bitflag<u8, M>>,
+    state: Option<SnapshotState>,
+}
+
+impl<M: MemoryBackend> Snapshot<M> {
+    pub fn new(
+        position: Option<PointerChain<[f32; 4], M>>,
+        values: Vec<NumericChain<M>>,
+        flags: Vec<Bitflag<u8, M>>,
+    ) -> Self {
+        Snapshot { position, values, flags, state: None }
+    }
+
+    pub fn is_captured(&self) -> bool {
+        self.state.is_some()
+    }
+
+    pub fn captured(&self) -> Option<&SnapshotState> {
+        self.state.as_ref()
+    }
+
+    fn read(&self) -> Result<SnapshotState, PointerChainError> {
+        Ok(SnapshotState {
+            position: self.position.as_ref().map(|p| p.read()).transpose()?,
+            values: self.values.iter().map(|v| v.read()).collect::<Result<_, _>>()?,
+            flags: self.flags.iter().map(|f| f.get()).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Captures the current state. The previous snapshot is kept if any
+    /// chain can't be read.
+    pub fn capture(&mut self) -> Result<(), PointerChainError> {
+        self.state = Some(self.read()?);
+        Ok(())
+    }
+
+    /// Restores the captured state. Does nothing if nothing was captured.
+    pub fn restore(&self) -> Result<(), PointerChainError> {
+        let Some(state) = &self.state else {
+            return Ok(());
+        };
+
+        // Check that everything is reachable before writing anything.
+        self.read()?;
+
+        for (flag, &value) in self.flags.iter().zip(&state.flags) {
+            flag.set(value)?;
+        }
+        for (chain, &value) in self.values.iter().zip(&state.values) {
+            chain.write(value)?;
+        }
+        if let (Some(chain), Some(position)) = (&self.position, state.position) {
+            chain.write(position)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FakeMemory;
+
+    #[test]
+    fn test_snapshot() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, [1.0f32, 2., 3., 0.5]);
+        memory.put(0x2000, 800i32);
+        memory.put(0x3000, 0b0000_0100u8);
+
+        let position = PointerChain::with_backend(memory.clone(), &[0x1000]);
+        let hp: PointerChain<i32, _> = PointerChain::with_backend(memory.clone(), &[0x2000]);
+        let flag = Bitflag::new(PointerChain::with_backend(memory.clone(), &[0x3000]), 0b100);
+        let mut snapshot = Snapshot::new(Some(position), vec![hp.into()], vec![flag]);
+
+        assert_eq!(snapshot.restore(), Ok(()));
+        assert!(!snapshot.is_captured());
+
+        snapshot.capture().unwrap();
+        memory.put(0x1000, [0.0f32; 4]);
+        memory.put(0x2000, 10i32);
+        memory.put(0x3000, 0b0000_0001u8);
+        snapshot.restore().unwrap();
+        assert_eq!(memory.get::<[f32; 4]>(0x1000), Some([1.0, 2., 3., 0.5]));
+        assert_eq!(memory.get::<i32>(0x2000), Some(800));
+        assert_eq!(memory.get::<u8>(0x3000), Some(0b0000_0101));
+        assert_eq!(
+            snapshot.captured(),
+            Some(&SnapshotState {
+                position: Some([1.0, 2., 3., 0.5]),
+                values: vec![800.],
+                flags: vec![true]
+            })
+        );
+
+        // Nothing is captured if part of the state is unreachable.
+        let unmapped = PointerChain::<f32, _>::with_backend(memory.clone(), &[0x4000]);
+        let mut snapshot = Snapshot::new(None, vec![unmapped.into()], vec![]);
+        assert!(snapshot.capture().is_err());
+        assert!(!snapshot.is_captured());
+    }
+}
@@ -1,8 +1,8 @@
 use std::ptr::null_mut;
 
 use lazy_static::lazy_static;
-use tracing::info;
-use widestring::U16CString;
+use tracing::{info, warn};
+use widestring::{U16CStr, U16CString};
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::MAX_PATH;
 use windows::Win32::Storage::FileSystem::{
@@ -13,10 +13,17 @@ use windows::Win32::System::LibraryLoader::{GetModuleFileNameW, GetModuleHandleW
 pub use crate::codegen::base_addresses::Version;
 
 lazy_static! {
-    pub static ref VERSION: Version = get_version();
+    /// File version of the running executable, known or not.
+    pub static ref FILE_VERSION: Option<(u32, u32, u32)> = get_current_file_version();
+    /// Version of the running game, if it is one of the known ones.
+    pub static ref VERSION: Option<Version> = get_version();
 }
 
-pub fn get_version() -> Version {
+/// The most recent known version. Its structure layouts are assumed for
+/// unknown versions, whose base addresses are found by scanning.
+pub const LATEST_VERSION: Version = Version::V1_06_0;
+
+fn get_current_file_version() -> Option<(u32, u32, u32)> {
     let file_path = {
         let mut buf = vec![0u16; MAX_PATH as usize];
         unsafe {
@@ -25,6 +32,24 @@ pub fn get_version() -> Version {
         U16CString::from_vec_truncate(buf)
     };
 
+    get_file_version(&file_path)
+}
+
+/// Detects the version of the running game. Returns `None` if the version
+/// couldn't be read or is not a known one.
+pub fn get_version() -> Option<Version> {
+    let Some((major, minor, patch)) = *FILE_VERSION else {
+        warn!("Couldn't read the game version");
+        return None;
+    };
+
+    info!("Version {} {} {}", major, minor, patch);
+    Version::try_from((major, minor, patch)).ok()
+}
+
+/// Reads the `major.minor.patch` file version out of the version resource of
+/// an executable.
+pub(crate) fn get_file_version(file_path: &U16CStr) -> Option<(u32, u32, u32)> {
     let mut version_info_size =
         unsafe { GetFileVersionInfoSizeW(PCWSTR(file_path.as_ptr()), None) };
     let mut version_info_buf = vec![0u8; version_info_size as usize];
@@ -35,7 +60,7 @@ pub fn get_version() -> Version {
             version_info_size,
             version_info_buf.as_mut_ptr() as _,
         )
-        .unwrap()
+        .ok()?
     };
 
     let mut version_info: *mut VS_FIXEDFILEINFO = null_mut();
@@ -47,11 +72,10 @@ pub fn get_version() -> Version {
             &mut version_info_size,
         )
     };
-    let version_info = unsafe { version_info.as_ref().unwrap() };
+    let version_info = unsafe { version_info.as_ref()? };
     let major = (version_info.dwFileVersionMS >> 16) & 0xffff;
     let minor = (version_info.dwFileVersionMS) & 0xffff;
     let patch = (version_info.dwFileVersionLS >> 16) & 0xffff;
 
-    info!("Version {} {} {}", major, minor, patch);
-    Version::try_from((major, minor, patch)).unwrap()
+    Some((major, minor, patch))
 }
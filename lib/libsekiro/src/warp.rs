@@ -0,0 +1,176 @@
+use crate::backend::{CurrentProcess, MemoryBackend};
+use crate::memedit::{PointerChain, PointerChainError};
+use crate::pointers::base_module_address;
+use crate::scan::CURRENT_BASE_ADDRESSES;
+
+/// Offset in `GameMan` of the ID of the idol to warp to.
+const WARP_DESTINATION: usize = 0xBF0;
+/// Offset in `GameMan` of the flag requesting the warp, which the game
+/// clears once it starts the loading screen.
+const WARP_REQUEST: usize = 0xBF4;
+
+/// A Sculptor's Idol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Idol {
+    pub id: u32,
+    pub area: &'static str,
+    pub name: &'static str,
+}
+
+macro_rules! idols {
+    ($( $area:literal => [ $( ($id:literal, $name:literal), )* ], )*) => {
+        /// All the idols, grouped by area in the order they are reached.
+        pub const IDOLS: &[Idol] = &[
+            $($(Idol { id: $id, area: $area, name: $name },)*)*
+        ];
+
+        /// The areas, in the order they appear in [`IDOLS`].
+        pub const AREAS: &[&str] = &[$($area,)*];
+    };
+}
+
+idols!(
+    "Dilapidated Temple" => [
+        (1111950, "Dilapidated Temple"),
+    ],
+    "Ashina Outskirts" => [
+        (1101950, "Ashina Outskirts"),
+        (1101951, "Outskirts Wall - Gate Path"),
+        (1101952, "Outskirts Wall - Stairway"),
+        (1101953, "Underbridge Valley"),
+        (1101954, "Ashina Castle Fortress"),
+        (1101955, "Ashina Castle Gate"),
+        (1101956, "Flames of Hatred"),
+    ],
+    "Hirata Estate" => [
+        (1001950, "Bamboo Thicket Slope"),
+        (1001951, "Hirata Estate - Main Hall"),
+        (1001952, "Hirata Audience Chamber"),
+        (1001953, "Hirata Estate - Hidden Temple"),
+        (1001954, "Estate Path"),
+    ],
+    "Ashina Castle" => [
+        (1111951, "Ashina Castle"),
+        (1111952, "Upper Tower - Antechamber"),
+        (1111953, "Upper Tower - Ashina Dojo"),
+        (1111954, "Castle Tower Lookout"),
+        (1111955, "Upper Tower - Kuro's Room"),
+        (1111956, "Old Grave"),
+        (1111957, "Great Serpent Shrine"),
+        (1111958, "Abandoned Dungeon Entrance"),
+        (1111959, "Ashina Reservoir"),
+        (1111960, "Near Secret Passage"),
+    ],
+    "Abandoned Dungeon" => [
+        (1121950, "Underground Waterway"),
+        (1121951, "Bottomless Hole"),
+    ],
+    "Senpou Temple" => [
+        (2001950, "Senpou Temple, Mt. Kongo"),
+        (2001951, "Shugendo"),
+        (2001952, "Temple Grounds"),
+        (2001953, "Main Hall"),
+        (2001954, "Inner Sanctum"),
+        (2001955, "Sunken Valley Cavern"),
+        (2001956, "Bell Demon's Temple"),
+    ],
+    "Sunken Valley" => [
+        (1701950, "Under-Shrine Valley"),
+        (1701951, "Sunken Valley"),
+        (1701952, "Gun Fort"),
+        (1701953, "Riven Cave"),
+        (1701954, "Bodhisattva Valley"),
+        (1701955, "Guardian Ape's Watering Hole"),
+    ],
+    "Ashina Depths" => [
+        (1301950, "Ashina Depths"),
+        (1301951, "Poison Pool"),
+        (1301952, "Guardian Ape's Burrow"),
+        (1301953, "Hidden Forest"),
+        (1301954, "Mibu Village"),
+        (1301955, "Water Mill"),
+        (1301956, "Wedding Cave Door"),
+    ],
+    "Fountainhead Palace" => [
+        (2501950, "Fountainhead Palace"),
+        (2501951, "Vermilion Bridge"),
+        (2501952, "Mibu Manor"),
+        (2501953, "Flower Viewing Stage"),
+        (2501954, "Great Sakura"),
+        (2501955, "Palace Grounds"),
+        (2501956, "Feeding Grounds"),
+        (2501957, "Near Pot Noble"),
+        (2501958, "Sanctuary"),
+    ],
+);
+
+impl Idol {
+    pub fn by_id(id: u32) -> Option<&'static Idol> {
+        IDOLS.iter().find(|idol| idol.id == id)
+    }
+
+    /// The idols of an area, in the order they are reached.
+    pub fn in_area(area: &str) -> impl Iterator<Item = &'static Idol> + '_ {
+        IDOLS.iter().filter(move |idol| idol.area == area)
+    }
+}
+
+/// Triggers the game's own warp to an idol, as if it was chosen from the
+/// idol menu.
+#[derive(Debug, Clone)]
+pub struct Warp<M: MemoryBackend = CurrentProcess> {
+    destination: PointerChain<u32, M>,
+    request: PointerChain<u8, M>,
+}
+
+impl Default for Warp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Warp {
+    pub fn new() -> Self {
+        let base_addresses = CURRENT_BASE_ADDRESSES.with_module_base_addr(base_module_address());
+        Self::with_backend(CurrentProcess, base_addresses.game_man)
+    }
+}
+
+impl<M: MemoryBackend> Warp<M> {
+    /// `game_man` is the absolute address of the static pointer to `GameMan`.
+    pub fn with_backend(backend: M, game_man: usize) -> Self {
+        Warp {
+            destination: PointerChain::with_backend(backend.clone(), &[game_man, WARP_DESTINATION]),
+            request: PointerChain::with_backend(backend, &[game_man, WARP_REQUEST]),
+        }
+    }
+
+    pub fn warp(&self, idol: &Idol) -> Result<(), PointerChainError> {
+        self.destination.write(idol.id)?;
+        self.request.write(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::FakeMemory;
+
+    #[test]
+    fn test_warp() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 0x2000usize);
+        memory.put(0x2000 + WARP_DESTINATION, [0u32; 2]);
+
+        let idol = Idol::by_id(1111950).unwrap();
+        assert_eq!(idol.name, "Dilapidated Temple");
+        assert_eq!(Warp::with_backend(memory.clone(), 0x1000).warp(idol), Ok(()));
+        assert_eq!(memory.get::<u32>(0x2000 + WARP_DESTINATION), Some(1111950));
+        assert_eq!(memory.get::<u8>(0x2000 + WARP_REQUEST), Some(1));
+
+        assert!(AREAS.iter().all(|area| Idol::in_area(area).count() > 0));
+        assert_eq!(IDOLS.iter().filter(|idol| Idol::by_id(idol.id) != Some(idol)).count(), 0);
+        let names = IDOLS.iter().map(|idol| idol.name).collect::<std::collections::HashSet<_>>();
+        assert_eq!(names.len(), IDOLS.len());
+    }
+}
@@ -1,6 +1,5 @@
 use std::ffi::c_void;
 use std::mem;
-use std::ptr::null_mut;
 
 use libsekiro::prelude::*;
 use once_cell::sync::Lazy;
@@ -8,10 +7,7 @@ use u16cstr::u16str;
 use widestring::U16CString;
 use windows::core::*;
 use windows::Win32::Foundation::*;
-use windows::Win32::System::LibraryLoader::{GetModuleHandleW, GetProcAddress, LoadLibraryW};
-use windows::Win32::System::Memory::{
-    VirtualProtect, PAGE_EXECUTE_READWRITE, PAGE_PROTECTION_FLAGS,
-};
+use windows::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryW};
 use windows::Win32::System::SystemInformation::GetSystemDirectoryW;
 use windows::Win32::System::SystemServices::DLL_PROCESS_ATTACH;
 
@@ -66,17 +62,11 @@ fn dinput8_path() -> U16CString {
     )
 }
 
-unsafe fn apply_patch() {
-    let module_base = GetModuleHandleW(PCWSTR(null_mut())).unwrap();
-    let offset = base_addresses::BaseAddresses::from(*VERSION).no_logo;
+static PATCHES: Lazy<CodePatches> = Lazy::new(CodePatches::new);
 
-    let ptr = (module_base.0 as usize + offset) as *mut [u8; 2];
-    let mut old = PAGE_PROTECTION_FLAGS(0);
-    if *ptr == [0x74, 0x30] {
-        VirtualProtect(ptr as _, 2, PAGE_EXECUTE_READWRITE, &mut old);
-        (*ptr) = [0x75, 0x30];
-        VirtualProtect(ptr as _, 2, old, &mut old);
-    }
+fn apply_patch() {
+    // There's nowhere to report a mismatch to: leave the game untouched.
+    PATCHES.no_logo.apply().ok();
 }
 
 /// # Safety
@@ -1,25 +1,64 @@
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 
 use hudhook::tracing::metadata::LevelFilter;
+use libsekiro::memedit::Bitflag as BitflagInner;
 use libsekiro::prelude::*;
 use practice_tool_core::key::Key;
 use practice_tool_core::widgets::Widget;
-use serde::Deserialize;
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use toml::Spanned;
 
 use crate::widgets::cycle_color::cycle_color;
 use crate::widgets::cycle_speed::cycle_speed;
+use crate::widgets::edit_value::edit_value;
+use crate::widgets::event_flag::{event_flag, event_flag_preset};
 use crate::widgets::flag::flag_widget;
+use crate::widgets::frame_advance::frame_advance;
+use crate::widgets::free_cam::free_cam;
+use crate::widgets::freeze::freeze_widget;
+use crate::widgets::game_speed::game_speed;
 use crate::widgets::group::group;
+use crate::widgets::id_scope;
 use crate::widgets::label::label_widget;
+use crate::widgets::no_clip::no_clip;
 use crate::widgets::nudge_pos::nudge_position;
 use crate::widgets::position::save_position;
+use crate::widgets::position_library::position_library;
 use crate::widgets::quitout::quitout;
+use crate::widgets::refill::refill;
 use crate::widgets::savefile_manager::savefile_manager;
+use crate::widgets::set_value::set_value;
+use crate::widgets::snapshot::snapshot;
+use crate::widgets::teleport::teleport;
+use crate::widgets::toggle_value::toggle_value;
+use crate::widgets::warp::warp;
+
+/// Name of the profile made of the top-level `commands`.
+const DEFAULT_PROFILE: &str = "Default";
 
 #[cfg_attr(test, derive(Debug))]
 #[derive(Deserialize)]
 pub(crate) struct Config {
     pub(crate) settings: Settings,
+    #[serde(default)]
+    pointers: BTreeMap<String, PointerSpec>,
+    #[serde(default)]
+    event_flag_presets: BTreeMap<String, EventFlagPresetSpec>,
+    commands: Vec<CfgCommand>,
+    #[serde(default)]
+    profiles: Vec<Profile>,
+    #[serde(skip)]
+    warnings: Vec<String>,
+}
+
+/// An alternative set of commands, selected in place of the top-level
+/// `commands`, which make up the default profile.
+#[cfg_attr(test, derive(Debug))]
+#[derive(Deserialize)]
+struct Profile {
+    name: String,
     commands: Vec<CfgCommand>,
 }
 
@@ -32,6 +71,9 @@ pub(crate) struct Settings {
     pub(crate) show_console: bool,
     #[serde(default = "Indicator::default_set")]
     pub(crate) indicators: Vec<Indicator>,
+    /// Name of the profile selected at startup.
+    pub(crate) profile: Option<String>,
+    pub(crate) cycle_profile: Option<Key>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -43,6 +85,30 @@ pub(crate) enum IndicatorType {
     ImguiDebug,
     Fps,
     FrameCount,
+    Hp,
+    Posture,
+    Target,
+    /// A pointer declared in the `[pointers]` section.
+    Pointer(String),
+}
+
+impl IndicatorType {
+    /// Name of the indicator in the config file.
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            IndicatorType::Igt => "igt",
+            IndicatorType::Position => "position",
+            IndicatorType::PositionChange => "position_change",
+            IndicatorType::GameVersion => "game_version",
+            IndicatorType::ImguiDebug => "imgui_debug",
+            IndicatorType::Fps => "fps",
+            IndicatorType::FrameCount => "framecount",
+            IndicatorType::Hp => "hp",
+            IndicatorType::Posture => "posture",
+            IndicatorType::Target => "target",
+            IndicatorType::Pointer(name) => name,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -53,6 +119,19 @@ pub(crate) struct Indicator {
 }
 
 impl Indicator {
+    const NAMES: &'static [&'static str] = &[
+        "igt",
+        "position",
+        "position_change",
+        "game_version",
+        "fps",
+        "framecount",
+        "hp",
+        "posture",
+        "target",
+        "imgui_debug",
+    ];
+
     fn default_set() -> Vec<Indicator> {
         vec![
             Indicator { indicator: IndicatorType::GameVersion, enabled: true },
@@ -61,6 +140,9 @@ impl Indicator {
             Indicator { indicator: IndicatorType::PositionChange, enabled: false },
             Indicator { indicator: IndicatorType::Fps, enabled: false },
             Indicator { indicator: IndicatorType::FrameCount, enabled: false },
+            Indicator { indicator: IndicatorType::Hp, enabled: false },
+            Indicator { indicator: IndicatorType::Posture, enabled: false },
+            Indicator { indicator: IndicatorType::Target, enabled: false },
             Indicator { indicator: IndicatorType::ImguiDebug, enabled: false },
         ]
     }
@@ -92,15 +174,26 @@ impl TryFrom<IndicatorConfig> for Indicator {
             "framecount" => {
                 Ok(Indicator { indicator: IndicatorType::FrameCount, enabled: indicator.enabled })
             },
+            "hp" => Ok(Indicator { indicator: IndicatorType::Hp, enabled: indicator.enabled }),
+            "posture" => {
+                Ok(Indicator { indicator: IndicatorType::Posture, enabled: indicator.enabled })
+            },
+            "target" => {
+                Ok(Indicator { indicator: IndicatorType::Target, enabled: indicator.enabled })
+            },
             "imgui_debug" => {
                 Ok(Indicator { indicator: IndicatorType::ImguiDebug, enabled: indicator.enabled })
             },
-            value => Err(format!("Unrecognized indicator: {value}")),
+            // Validated against the `[pointers]` section in `Config::validate`.
+            value => Ok(Indicator {
+                indicator: IndicatorType::Pointer(value.to_string()),
+                enabled: indicator.enabled,
+            }),
         }
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 #[serde(untagged)]
 enum PlaceholderOption<T> {
     Data(T),
@@ -118,7 +211,7 @@ impl<T> PlaceholderOption<T> {
 }
 
 #[cfg_attr(test, derive(Debug))]
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 #[serde(untagged)]
 enum CfgCommand {
     SavefileManager {
@@ -129,15 +222,71 @@ enum CfgCommand {
         flag: FlagSpec,
         hotkey: Option<Key>,
     },
+    Freeze {
+        freeze: ValueSpec,
+        value: Option<f64>,
+        hotkey: Option<Key>,
+    },
+    SetValue {
+        set_value: ValueSpec,
+        value: f64,
+        hotkey: Option<Key>,
+    },
+    ToggleValue {
+        toggle_value: ValueSpec,
+        values: [f64; 2],
+        hotkey: Option<Key>,
+    },
+    EditValue {
+        edit_value: ValueSpec,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Refill {
+        refill: RefillSpec,
+        hotkey: Option<Key>,
+    },
+    EventFlag {
+        event_flag: u32,
+        label: Option<String>,
+        hotkey: Option<Key>,
+    },
+    EventFlagPreset {
+        event_flag_preset: String,
+        hotkey: Option<Key>,
+    },
     Position {
         position: PlaceholderOption<Key>,
         save: Option<Key>,
     },
+    Snapshot {
+        #[serde(rename = "snapshot")]
+        hotkey_load: PlaceholderOption<Key>,
+        save: Option<Key>,
+        label: Option<String>,
+        #[serde(default = "default_snapshot_values")]
+        values: Vec<SnapshotValue>,
+        #[serde(default)]
+        flags: Vec<FlagSpec>,
+    },
+    Teleport {
+        #[serde(rename = "teleport")]
+        hotkey: PlaceholderOption<Key>,
+    },
+    PositionLibrary {
+        #[serde(rename = "position_library")]
+        hotkey_load: PlaceholderOption<Key>,
+    },
     CycleSpeed {
         #[serde(rename = "cycle_speed")]
         values: Vec<f32>,
         hotkey: Option<Key>,
     },
+    GameSpeed {
+        #[serde(rename = "game_speed")]
+        values: Vec<f32>,
+        hotkey: Option<Key>,
+    },
     CycleColor {
         #[serde(rename = "cycle_color")]
         cycle_color: Vec<i32>,
@@ -151,6 +300,37 @@ enum CfgCommand {
         nudge: f32,
         nudge_up: Option<Key>,
         nudge_down: Option<Key>,
+        nudge_forward: Option<Key>,
+        nudge_back: Option<Key>,
+        nudge_left: Option<Key>,
+        nudge_right: Option<Key>,
+    },
+    FrameAdvance {
+        #[serde(rename = "frame_advance")]
+        hotkey_pause: PlaceholderOption<Key>,
+        step: Option<Key>,
+        step_n: Option<Key>,
+        #[serde(default = "default_step_frames")]
+        frames: u32,
+    },
+    FreeCam {
+        #[serde(rename = "free_cam")]
+        hotkey_toggle: PlaceholderOption<Key>,
+        #[serde(rename = "return")]
+        hotkey_return: Option<Key>,
+        #[serde(default = "default_fly_speed")]
+        speed: f32,
+    },
+    NoClip {
+        #[serde(rename = "no_clip")]
+        hotkey: PlaceholderOption<Key>,
+        #[serde(default = "default_fly_speed")]
+        speed: f32,
+    },
+    Warp {
+        #[serde(rename = "warp")]
+        hotkey: PlaceholderOption<Key>,
+        idol: Option<u32>,
     },
     Quitout {
         #[serde(rename = "quitout")]
@@ -163,35 +343,467 @@ enum CfgCommand {
     },
 }
 
-impl CfgCommand {
-    fn into_widget(self, settings: &Settings, chains: &Pointers) -> Box<dyn Widget> {
+/// Keys of each [`CfgCommand`]: the key naming the command, then its
+/// options. Only used to explain why a command couldn't be parsed, as the
+/// untagged enum doesn't say which variant was meant. Commands are
+/// recognized by the first name found in a table, hence `label` comes last.
+const COMMAND_KEYS: &[(&str, &[&str])] = &[
+    ("savefile_manager", &[]),
+    ("flag", &["hotkey"]),
+    ("freeze", &["value", "hotkey"]),
+    ("set_value", &["value", "hotkey"]),
+    ("toggle_value", &["values", "hotkey"]),
+    ("edit_value", &["min", "max"]),
+    ("refill", &["hotkey"]),
+    ("event_flag", &["label", "hotkey"]),
+    ("event_flag_preset", &["hotkey"]),
+    ("position", &["save"]),
+    ("snapshot", &["save", "label", "values", "flags"]),
+    ("teleport", &[]),
+    ("position_library", &[]),
+    ("cycle_speed", &["hotkey"]),
+    ("game_speed", &["hotkey"]),
+    ("cycle_color", &["hotkey"]),
+    ("nudge", &[
+        "nudge_up",
+        "nudge_down",
+        "nudge_forward",
+        "nudge_back",
+        "nudge_left",
+        "nudge_right",
+    ]),
+    ("frame_advance", &["step", "step_n", "frames"]),
+    ("free_cam", &["return", "speed"]),
+    ("no_clip", &["speed"]),
+    ("warp", &["idol"]),
+    ("quitout", &[]),
+    ("group", &["commands"]),
+    ("label", &[]),
+];
+
+/// A TOML document reduced to its structure, with the location of every
+/// key and array element, to point errors at the right place.
+enum Spans {
+    Table(Vec<(Spanned<String>, Spanned<Spans>)>),
+    Array(Vec<Spanned<Spans>>),
+    Value,
+}
+
+impl<'de> Deserialize<'de> for Spans {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SpansVisitor;
+
+        impl<'de> Visitor<'de> for SpansVisitor {
+            type Value = Spans;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a TOML value")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Spans, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(key) = map.next_key()? {
+                    entries.push((key, map.next_value()?));
+                }
+                Ok(Spans::Table(entries))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Spans, A::Error> {
+                let mut elements = Vec::new();
+                while let Some(element) = seq.next_element()? {
+                    elements.push(element);
+                }
+                Ok(Spans::Array(elements))
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<Spans, E> {
+                Ok(Spans::Value)
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<Spans, E> {
+                Ok(Spans::Value)
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<Spans, E> {
+                Ok(Spans::Value)
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<Spans, E> {
+                Ok(Spans::Value)
+            }
+
+            fn visit_str<E>(self, _: &str) -> Result<Spans, E> {
+                Ok(Spans::Value)
+            }
+        }
+
+        deserializer.deserialize_any(SpansVisitor)
+    }
+}
+
+impl Spans {
+    fn get(&self, key: &str) -> Option<&Spans> {
+        match self {
+            Spans::Table(entries) => {
+                entries.iter().find(|(k, _)| k.get_ref() == key).map(|(_, v)| v.get_ref())
+            },
+            _ => None,
+        }
+    }
+
+    fn elements(&self) -> &[Spanned<Spans>] {
         match self {
-            CfgCommand::Flag { flag, hotkey: key } => {
-                flag_widget(&flag.label, (flag.getter)(chains).clone(), key)
+            Spans::Array(elements) => elements,
+            _ => &[],
+        }
+    }
+
+    /// Records the offset of every key and element under `path`, with
+    /// paths formatted like `commands[0].flag`.
+    fn collect(&self, path: &str, offsets: &mut HashMap<String, usize>) {
+        match self {
+            Spans::Table(entries) => {
+                for (key, value) in entries {
+                    let path = match path {
+                        "" => key.get_ref().clone(),
+                        path => format!("{path}.{}", key.get_ref()),
+                    };
+                    offsets.insert(path.clone(), key.span().start);
+                    value.get_ref().collect(&path, offsets);
+                }
+            },
+            Spans::Array(elements) => {
+                for (i, element) in elements.iter().enumerate() {
+                    let path = format!("{path}[{i}]");
+                    offsets.insert(path.clone(), element.span().start);
+                    element.get_ref().collect(&path, offsets);
+                }
+            },
+            Spans::Value => {},
+        }
+    }
+}
+
+/// Locations of the keys and array elements of a configuration, by path.
+struct Locations<'a> {
+    cfg: &'a str,
+    spans: Option<Spans>,
+    offsets: HashMap<String, usize>,
+}
+
+impl<'a> Locations<'a> {
+    fn new(cfg: &'a str) -> Self {
+        let spans = toml::from_str::<Spans>(cfg).ok();
+        let mut offsets = HashMap::new();
+        if let Some(spans) = &spans {
+            spans.collect("", &mut offsets);
+        }
+        Locations { cfg, spans, offsets }
+    }
+
+    /// Formats the line and column of the key or element at `path`, or
+    /// nothing if there is none.
+    fn of(&self, path: &str) -> String {
+        self.offsets.get(path).map(|&offset| line_column(self.cfg, offset)).unwrap_or_default()
+    }
+
+    /// Explains what's wrong with the keys of each command, which
+    /// deserializing ignores or, for the untagged command enum, doesn't
+    /// explain. Every message starts with the path of the command.
+    fn diagnose_commands(&self) -> Vec<String> {
+        let mut diagnoses = Vec::new();
+        let Some(spans) = &self.spans else { return diagnoses };
+
+        if let Some(commands) = spans.get("commands") {
+            self.diagnose(commands.elements(), "commands", &mut diagnoses);
+        }
+        let profiles = spans.get("profiles").map(Spans::elements).unwrap_or_default();
+        for (i, profile) in profiles.iter().enumerate() {
+            if let Some(commands) = profile.get_ref().get("commands") {
+                self.diagnose(
+                    commands.elements(),
+                    &format!("profiles[{i}].commands"),
+                    &mut diagnoses,
+                );
+            }
+        }
+        diagnoses
+    }
+
+    fn diagnose(&self, commands: &[Spanned<Spans>], path: &str, diagnoses: &mut Vec<String>) {
+        for (i, command) in commands.iter().enumerate() {
+            let path = format!("{path}[{i}]");
+            let Spans::Table(entries) = command.get_ref() else {
+                diagnoses.push(format!(
+                    "{path}{}: a command must be a table, e.g. {{ flag = \"...\" }}",
+                    self.of(&path)
+                ));
+                continue;
+            };
+            let keys = entries.iter().map(|(key, _)| key.get_ref().as_str()).collect::<Vec<_>>();
+            let location = |key: &str| self.of(&format!("{path}.{key}"));
+
+            let Some((name, options)) = COMMAND_KEYS.iter().find(|(name, _)| keys.contains(name))
+            else {
+                let names = COMMAND_KEYS.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+                let is_option = |key: &&str| COMMAND_KEYS.iter().any(|(_, o)| o.contains(key));
+                let unknown = keys.iter().filter(|key| !is_option(key)).collect::<Vec<_>>();
+                if unknown.is_empty() {
+                    diagnoses.push(format!(
+                        "{path}{}: missing the command, e.g. {{ flag = \"...\" }}",
+                        self.of(&path)
+                    ));
+                }
+                for key in unknown {
+                    diagnoses.push(format!(
+                        "{path}{}: unknown command \"{key}\"{}",
+                        location(key),
+                        did_you_mean(key, &names)
+                    ));
+                }
+                continue;
+            };
+
+            let known = options.iter().copied().chain([*name]).collect::<Vec<_>>();
+            for key in keys.iter().filter(|key| !known.contains(key)) {
+                diagnoses.push(format!(
+                    "{path}{}: unknown key \"{key}\" in \"{name}\" command{}",
+                    location(key),
+                    did_you_mean(key, &known)
+                ));
+            }
+
+            if *name == "group" {
+                if let Some(commands) = command.get_ref().get("commands") {
+                    self.diagnose(commands.elements(), &format!("{path}.commands"), diagnoses);
+                }
+            }
+        }
+    }
+}
+
+/// Suggests the candidate closest to `name`, if any is close enough to be
+/// a typo.
+fn did_you_mean(name: &str, candidates: &[&str]) -> String {
+    // Optimal string alignment distance, i.e. Levenshtein distance where
+    // swapping two adjacent characters counts as a single edit.
+    fn distance(a: &str, b: &str) -> usize {
+        let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+        let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for (i, row) in d.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        d[0] = (0..=b.len()).collect();
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = (a[i - 1] != b[j - 1]) as usize;
+                d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        d[a.len()][b.len()]
+    }
+
+    candidates
+        .iter()
+        .map(|c| (distance(name, c), c))
+        .filter(|(d, _)| *d <= (name.len() / 3).max(1))
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| format!("; did you mean \"{c}\"?"))
+        .unwrap_or_default()
+}
+
+/// Formats a byte offset in the configuration as a 1-based line and column.
+fn line_column(cfg: &str, offset: usize) -> String {
+    let before = &cfg[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    format!(" (line {line}, column {column})")
+}
+
+fn default_snapshot_values() -> Vec<SnapshotValue> {
+    ["position", "hp", "posture", "anim_speed", "resurrections"]
+        .map(|name| SnapshotValue::from(name.to_string()))
+        .into()
+}
+
+fn default_step_frames() -> u32 {
+    10
+}
+
+fn default_fly_speed() -> f32 {
+    10.
+}
+
+impl CfgCommand {
+    /// Returns `None` if the command relies on pointers which aren't
+    /// available on the running game version.
+    fn into_widget<M: MemoryBackend>(
+        self,
+        config: &Config,
+        ctx: &CommandContext<M>,
+    ) -> Option<Box<dyn Widget>> {
+        let CommandContext {
+            chains,
+            user_pointers,
+            freezer,
+            event_flags,
+            warp: warp_ctx,
+            frame_advance: frame_advance_ctx,
+            camera,
+        } = *ctx;
+        let widget = match self {
+            CfgCommand::Flag { flag, hotkey: key } => match flag.get(chains) {
+                Some(bitflag) => flag_widget(&flag.label, bitflag.clone(), key),
+                None if FlagSpec::NAMES.contains(&flag.name.as_str()) => return None,
+                None => user_pointers[&flag.name].flag_widget(key).unwrap(),
+            },
+            CfgCommand::Freeze { freeze, value, hotkey } => {
+                let (label, chain) = freeze.resolve(chains, user_pointers)?;
+                freeze_widget(&label, chain, value, freezer, hotkey)
+            },
+            CfgCommand::SetValue { set_value: spec, value, hotkey } => {
+                let (label, chain) = spec.resolve(chains, user_pointers)?;
+                set_value(&label, chain, value, hotkey)
+            },
+            CfgCommand::ToggleValue { toggle_value: spec, values, hotkey } => {
+                let (label, chain) = spec.resolve(chains, user_pointers)?;
+                toggle_value(&label, chain, values, hotkey)
+            },
+            CfgCommand::EditValue { edit_value: spec, min, max } => {
+                let (label, chain) = spec.resolve(chains, user_pointers)?;
+                edit_value(&label, chain, min.zip(max))
+            },
+            CfgCommand::EventFlag { event_flag: id, label, hotkey } => {
+                let label = label.unwrap_or_else(|| format!("Event flag {id}"));
+                event_flag(&label, id, event_flags.clone(), hotkey)
+            },
+            CfgCommand::EventFlagPreset { event_flag_preset: name, hotkey } => {
+                let preset = &config.event_flag_presets[&name];
+                event_flag_preset(
+                    preset.label.as_deref().unwrap_or(&name),
+                    preset.on.clone(),
+                    preset.off.clone(),
+                    event_flags.clone(),
+                    hotkey,
+                )
             },
             CfgCommand::SavefileManager { hotkey_load: key_load } => {
-                savefile_manager(key_load.into_option(), settings.display)
+                savefile_manager(key_load.into_option(), config.settings.display)
+            },
+            CfgCommand::Refill { refill: spec, hotkey } => {
+                let chr = chains.chr.as_ref()?;
+                let (label, value, max) = match spec {
+                    RefillSpec::Hp => ("HP", &chr.hp, &chr.max_hp),
+                    RefillSpec::Posture => ("Posture", &chr.posture, &chr.max_posture),
+                    RefillSpec::TargetHp => ("Target HP", &chr.target_hp, &chr.target_max_hp),
+                    RefillSpec::TargetPosture => {
+                        ("Target Posture", &chr.target_posture, &chr.target_max_posture)
+                    },
+                };
+                refill(label, value.clone(), max.clone(), hotkey)
             },
             CfgCommand::Position { position, save } => {
                 save_position(chains.position.clone(), position.into_option(), save)
             },
-            CfgCommand::Label { label } => label_widget(label.as_str()),
-            CfgCommand::NudgePosition { nudge, nudge_up, nudge_down } => {
-                nudge_position(chains.position.clone(), nudge, nudge_up, nudge_down)
+            CfgCommand::Snapshot { hotkey_load, save, label, values, flags } => {
+                let mut position = None;
+                let mut value_chains = Vec::new();
+                let mut value_labels = Vec::new();
+                for value in values {
+                    match value {
+                        SnapshotValue::Position => position = Some(chains.position.clone()),
+                        SnapshotValue::Value(spec) => {
+                            let Some((label, chain)) = spec.resolve(chains, user_pointers) else {
+                                continue;
+                            };
+                            value_labels.push((label, chain.value_type()));
+                            value_chains.push(chain);
+                        },
+                    }
+                }
+                let (flag_labels, flags) = flags
+                    .iter()
+                    .filter_map(|f| Some((f.label.clone(), f.get(chains)?.clone())))
+                    .unzip();
+
+                snapshot(
+                    label.as_deref().unwrap_or("Snapshot"),
+                    Snapshot::new(position, value_chains, flags),
+                    value_labels,
+                    flag_labels,
+                    hotkey_load.into_option(),
+                    save,
+                )
+            },
+            CfgCommand::Teleport { hotkey } => {
+                teleport(chains.position.clone(), hotkey.into_option())
             },
+            CfgCommand::PositionLibrary { hotkey_load } => position_library(
+                chains.position.clone(),
+                hotkey_load.into_option(),
+                config.settings.display,
+            ),
+            CfgCommand::Label { label } => label_widget(label.as_str()),
+            CfgCommand::NudgePosition {
+                nudge,
+                nudge_up,
+                nudge_down,
+                nudge_forward,
+                nudge_back,
+                nudge_left,
+                nudge_right,
+            } => nudge_position(chains.position.clone(), nudge, [
+                nudge_up,
+                nudge_down,
+                nudge_forward,
+                nudge_back,
+                nudge_left,
+                nudge_right,
+            ]),
             CfgCommand::CycleSpeed { values, hotkey } => {
-                cycle_speed(values.as_slice(), chains.anim_speed.clone(), hotkey)
+                cycle_speed("Speed", values.as_slice(), chains.anim_speed.clone(), hotkey)
             },
+            CfgCommand::GameSpeed { values, hotkey } => game_speed(
+                values.as_slice(),
+                chains.game_speed.clone(),
+                frame_advance_ctx.clone(),
+                hotkey,
+            ),
             CfgCommand::CycleColor { cycle_color: values, hotkey } => {
                 cycle_color(values.as_slice(), chains.debug_color.clone(), hotkey)
             },
+            CfgCommand::FrameAdvance { hotkey_pause, step, step_n, frames } => frame_advance(
+                frame_advance_ctx.clone(),
+                frames,
+                hotkey_pause.into_option(),
+                step,
+                step_n,
+            ),
+            CfgCommand::FreeCam { hotkey_toggle, hotkey_return, speed } => {
+                free_cam(camera.clone(), speed, hotkey_toggle.into_option(), hotkey_return)
+            },
+            CfgCommand::NoClip { hotkey, speed } => no_clip(
+                chains.position.clone(),
+                chains.chr.as_ref()?.no_collision.clone(),
+                chains.chr.as_ref()?.no_gravity.clone(),
+                camera.clone(),
+                speed,
+                hotkey.into_option(),
+            ),
+            CfgCommand::Warp { hotkey, idol } => {
+                warp(warp_ctx.clone(), idol.and_then(Idol::by_id), hotkey.into_option())
+            },
             CfgCommand::Quitout { hotkey } => quitout(chains.quitout.clone(), hotkey.into_option()),
             CfgCommand::Group { label, commands } => group(
                 label.as_str(),
-                commands.into_iter().map(|c| c.into_widget(settings, chains)).collect(),
-                settings.display,
+                commands.into_iter().filter_map(|c| c.into_widget(config, ctx)).collect(),
+                config.settings.display,
             ),
-        }
+        };
+        Some(id_scope(widget))
     }
 }
 
@@ -216,13 +828,391 @@ impl TryFrom<String> for LevelFilterSerde {
     }
 }
 
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(try_from = "String")]
+struct ValueTypeSerde(ValueType);
+
+impl TryFrom<String> for ValueTypeSerde {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        ValueType::from_str(&value).map(ValueTypeSerde)
+    }
+}
+
+/// The game state the commands operate on.
+pub(crate) struct CommandContext<'a, M: MemoryBackend = CurrentProcess> {
+    pub(crate) chains: &'a Pointers<M>,
+    pub(crate) user_pointers: &'a UserPointers<M>,
+    pub(crate) freezer: &'a Freezer,
+    pub(crate) event_flags: &'a EventFlags<M>,
+    pub(crate) warp: &'a Warp<M>,
+    pub(crate) frame_advance: &'a FrameAdvance<M>,
+    pub(crate) camera: &'a CameraPointers<M>,
+}
+
+/// Event flags to set and clear at once, to put a save into a known state
+/// of a route.
+#[derive(Debug, Deserialize)]
+struct EventFlagPresetSpec {
+    label: Option<String>,
+    #[serde(default)]
+    on: Vec<u32>,
+    #[serde(default)]
+    off: Vec<u32>,
+}
+
+/// A pointer chain declared in the `[pointers]` section. It can be used by
+/// name wherever a built-in flag, value or indicator is expected.
+#[derive(Debug, Deserialize)]
+struct PointerSpec {
+    base: String,
+    #[serde(default)]
+    offsets: Vec<usize>,
+    #[serde(rename = "type")]
+    value_type: ValueTypeSerde,
+    mask: Option<u64>,
+    label: Option<String>,
+}
+
+/// A `[pointers]` entry resolved against the game's base addresses.
+pub(crate) struct UserPointer<M: MemoryBackend = CurrentProcess> {
+    pub(crate) label: String,
+    pub(crate) chain: NumericChain<M>,
+    pub(crate) mask: Option<u64>,
+}
+
+pub(crate) type UserPointers<M = CurrentProcess> = BTreeMap<String, UserPointer<M>>;
+
+/// Binds `$flag` to a [`BitflagInner`] over the integer chain `$chain` and
+/// evaluates to `Some($body)`, or to `None` for float chains.
+macro_rules! with_bitflag {
+    ($chain:expr, $mask:expr, |$flag:ident| $body:expr) => {
+        match $chain {
+            NumericChain::U8(c) => {
+                let $flag = BitflagInner::new(c.clone(), $mask as u8);
+                Some($body)
+            },
+            NumericChain::U16(c) => {
+                let $flag = BitflagInner::new(c.clone(), $mask as u16);
+                Some($body)
+            },
+            NumericChain::U32(c) => {
+                let $flag = BitflagInner::new(c.clone(), $mask as u32);
+                Some($body)
+            },
+            NumericChain::U64(c) => {
+                let $flag = BitflagInner::new(c.clone(), $mask);
+                Some($body)
+            },
+            NumericChain::I8(c) => {
+                let $flag = BitflagInner::new(c.clone(), $mask as i8);
+                Some($body)
+            },
+            NumericChain::I16(c) => {
+                let $flag = BitflagInner::new(c.clone(), $mask as i16);
+                Some($body)
+            },
+            NumericChain::I32(c) => {
+                let $flag = BitflagInner::new(c.clone(), $mask as i32);
+                Some($body)
+            },
+            NumericChain::I64(c) => {
+                let $flag = BitflagInner::new(c.clone(), $mask as i64);
+                Some($body)
+            },
+            NumericChain::F32(_) | NumericChain::F64(_) => None,
+        }
+    };
+}
+
+impl<M: MemoryBackend> UserPointer<M> {
+    /// Builds a flag widget toggling the masked bits. Returns `None` if the
+    /// pointer has no mask.
+    fn flag_widget(&self, key: Option<Key>) -> Option<Box<dyn Widget>> {
+        let mask = self.mask?;
+        with_bitflag!(&self.chain, mask, |flag| flag_widget(&self.label, flag, key))
+    }
+
+    /// Formats the current value for the indicators: the state of the
+    /// masked bits if there is a mask, the number otherwise.
+    pub(crate) fn display(&self) -> Result<String, PointerChainError> {
+        let flag = self.mask.and_then(|mask| with_bitflag!(&self.chain, mask, |flag| flag.get()));
+        match flag {
+            Some(state) => Ok(if state? { "on" } else { "off" }.to_string()),
+            None => self.chain.read().map(|v| self.chain.value_type().format(v)),
+        }
+    }
+}
+
 impl Config {
     pub(crate) fn parse(cfg: &str) -> Result<Self, String> {
-        toml::from_str::<Config>(cfg).map_err(|e| format!("TOML configuration parse error: {}", e))
+        let locations = Locations::new(cfg);
+        let diagnoses = locations.diagnose_commands();
+        let mut config = serde_path_to_error::deserialize::<_, Config>(toml::Deserializer::new(
+            cfg,
+        ))
+        .map_err(|e| {
+            let path = e.path().to_string();
+            // The untagged command enum doesn't say what's wrong.
+            let diagnosis = diagnoses.iter().find(|diagnosis| {
+                diagnosis.starts_with(&format!("{path} "))
+                    || diagnosis.starts_with(&format!("{path}:"))
+                    || diagnosis.starts_with(&format!("{path}."))
+            });
+            let message = match diagnosis {
+                Some(diagnosis) => diagnosis.clone(),
+                None => {
+                    // The span of the inner error can be the whole array of
+                    // the failing element.
+                    let mut location = locations.of(&path);
+                    if location.is_empty() {
+                        location =
+                            e.inner().span().map(|s| line_column(cfg, s.start)).unwrap_or_default();
+                    }
+                    match path.as_str() {
+                        "." => format!("{}{location}", e.inner().message()),
+                        _ => format!("{path}{location}: {}", e.inner().message()),
+                    }
+                },
+            };
+            format!("TOML configuration parse error: {message}")
+        })?;
+        config.validate(&locations).map_err(|e| format!("Configuration error: {}", e))?;
+        // Unknown keys have always been ignored, so they don't make the
+        // configuration invalid.
+        config.warnings = diagnoses;
+        Ok(config)
     }
 
-    pub(crate) fn make_commands(self, chains: &Pointers) -> Vec<Box<dyn Widget>> {
-        self.commands.into_iter().map(|c| c.into_widget(&self.settings, chains)).collect()
+    /// Problems which don't prevent using the configuration, such as
+    /// unknown keys in commands.
+    pub(crate) fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Checks the names referring to the `[pointers]` section, which can't
+    /// be checked while deserializing.
+    fn validate(&self, locations: &Locations) -> Result<(), String> {
+        for (name, spec) in &self.pointers {
+            // Built-in names are resolved first, so the pointer would never
+            // be used.
+            let builtin = [
+                ("flag", FlagSpec::NAMES),
+                ("value", ValueSpec::NAMES),
+                ("indicator", Indicator::NAMES),
+            ]
+            .into_iter()
+            .find(|(_, names)| names.contains(&name.as_str()));
+            if let Some((what, _)) = builtin {
+                return Err(format!(
+                    "pointer \"{name}\"{}: the name is already used by a built-in {what}",
+                    locations.of(&format!("pointers.{name}"))
+                ));
+            }
+            let symbols = base_addresses::BaseAddresses::SYMBOLS;
+            if !symbols.contains(&spec.base.as_str()) {
+                let hint = match did_you_mean(&spec.base, symbols) {
+                    hint if hint.is_empty() => format!(", expected one of: {}", symbols.join(", ")),
+                    hint => hint,
+                };
+                return Err(format!(
+                    "pointer \"{name}\"{}: \"{}\" is not a valid base{hint}",
+                    locations.of(&format!("pointers.{name}.base")),
+                    spec.base,
+                ));
+            }
+            if spec.mask.is_some() && !spec.value_type.0.is_integer() {
+                return Err(format!(
+                    "pointer \"{name}\": a mask requires an integer type, not {}",
+                    spec.value_type.0
+                ));
+            }
+            if let Some(mask) = spec.mask {
+                let fits = match spec.value_type.0 {
+                    ValueType::U8 | ValueType::I8 => u8::try_from(mask).is_ok(),
+                    ValueType::U16 | ValueType::I16 => u16::try_from(mask).is_ok(),
+                    ValueType::U32 | ValueType::I32 => u32::try_from(mask).is_ok(),
+                    _ => true,
+                };
+                if !fits {
+                    return Err(format!(
+                        "pointer \"{name}\": mask {mask:#x} doesn't fit in {}",
+                        spec.value_type.0
+                    ));
+                }
+            }
+        }
+
+        let pointer_names = self.pointers.keys().map(String::as_str);
+        let flag_names = FlagSpec::NAMES
+            .iter()
+            .copied()
+            .chain(self.pointers.iter().filter(|(_, p)| p.mask.is_some()).map(|(n, _)| n.as_str()))
+            .collect::<Vec<_>>();
+        let value_names =
+            ValueSpec::NAMES.iter().copied().chain(pointer_names.clone()).collect::<Vec<_>>();
+        let indicator_names =
+            Indicator::NAMES.iter().copied().chain(pointer_names).collect::<Vec<_>>();
+
+        for (i, indicator) in self.settings.indicators.iter().enumerate() {
+            if let IndicatorType::Pointer(name) = &indicator.indicator {
+                if !self.pointers.contains_key(name) {
+                    return Err(format!(
+                        "Unrecognized indicator{}: \"{name}\"{}",
+                        locations.of(&format!("settings.indicators[{i}]")),
+                        did_you_mean(name, &indicator_names)
+                    ));
+                }
+            }
+        }
+
+        let profile_names = self.profile_names();
+        for (i, profile) in self.profiles.iter().enumerate() {
+            if profile_names[..=i].contains(&profile.name.as_str()) {
+                return Err(format!(
+                    "profiles[{i}]{}: profile name \"{}\" is already in use",
+                    locations.of(&format!("profiles[{i}].name")),
+                    profile.name
+                ));
+            }
+        }
+        if let Some(name) = &self.settings.profile {
+            if self.profile_index(name).is_none() {
+                return Err(format!(
+                    "Unrecognized profile{}: \"{name}\"{}",
+                    locations.of("settings.profile"),
+                    did_you_mean(name, &profile_names)
+                ));
+            }
+        }
+
+        let invalid = |path: &str, what: &str, name: &str, candidates: &[&str]| {
+            format!(
+                "{path}{}: \"{name}\" is not a valid {what}{}",
+                locations.of(path),
+                did_you_mean(name, candidates)
+            )
+        };
+
+        let pointers = &self.pointers;
+        let check = |command: &CfgCommand, path: &str| match command {
+            CfgCommand::Flag { flag, .. } if !flag.is_valid(pointers) => {
+                Err(invalid(path, "flag specifier", &flag.name, &flag_names))
+            },
+            CfgCommand::Freeze { freeze, .. } if !freeze.is_valid(pointers) => {
+                Err(invalid(path, "freeze specifier", &freeze.0, &value_names))
+            },
+            CfgCommand::SetValue { set_value: spec, .. }
+            | CfgCommand::ToggleValue { toggle_value: spec, .. }
+            | CfgCommand::EditValue { edit_value: spec, .. }
+                if !spec.is_valid(pointers) =>
+            {
+                Err(invalid(path, "value specifier", &spec.0, &value_names))
+            },
+            CfgCommand::EditValue { edit_value: spec, min, max }
+                if min.is_some() != max.is_some() =>
+            {
+                Err(format!(
+                    "{path}{}: edit_value \"{}\": min and max must be set together",
+                    locations.of(path),
+                    spec.0
+                ))
+            },
+            CfgCommand::EventFlagPreset { event_flag_preset: name, .. }
+                if !self.event_flag_presets.contains_key(name) =>
+            {
+                let presets = self.event_flag_presets.keys().map(String::as_str);
+                Err(invalid(path, "event flag preset", name, &presets.collect::<Vec<_>>()))
+            },
+            CfgCommand::Warp { idol: Some(id), .. } if Idol::by_id(*id).is_none() => {
+                Err(format!("{path}: \"{id}\" is not a valid idol ID"))
+            },
+            CfgCommand::Snapshot { values, flags, .. } => {
+                for (i, value) in values.iter().enumerate() {
+                    match value {
+                        SnapshotValue::Value(spec) if !spec.is_valid(pointers) => {
+                            let mut names = value_names.clone();
+                            names.push("position");
+                            let path = format!("{path}.values[{i}]");
+                            return Err(invalid(&path, "snapshot value", &spec.0, &names));
+                        },
+                        _ => {},
+                    }
+                }
+                match flags.iter().position(|f| !FlagSpec::NAMES.contains(&f.name.as_str())) {
+                    Some(i) => Err(format!(
+                        "{}; snapshots can capture user-declared pointers as values",
+                        invalid(
+                            &format!("{path}.flags[{i}]"),
+                            "built-in flag",
+                            &flags[i].name,
+                            FlagSpec::NAMES
+                        )
+                    )),
+                    None => Ok(()),
+                }
+            },
+            _ => Ok(()),
+        };
+
+        fn walk(
+            commands: &[CfgCommand],
+            path: &str,
+            check: &dyn Fn(&CfgCommand, &str) -> Result<(), String>,
+        ) -> Result<(), String> {
+            for (i, command) in commands.iter().enumerate() {
+                let path = format!("{path}[{i}]");
+                check(command, &path)?;
+                if let CfgCommand::Group { commands, .. } = command {
+                    walk(commands, &format!("{path}.commands"), check)?;
+                }
+            }
+            Ok(())
+        }
+
+        walk(&self.commands, "commands", &check)?;
+        for (i, profile) in self.profiles.iter().enumerate() {
+            walk(&profile.commands, &format!("profiles[{i}].commands"), &check)?;
+        }
+        Ok(())
+    }
+
+    /// Names of the profiles, the default one first.
+    pub(crate) fn profile_names(&self) -> Vec<&str> {
+        std::iter::once(DEFAULT_PROFILE)
+            .chain(self.profiles.iter().map(|p| p.name.as_str()))
+            .collect()
+    }
+
+    pub(crate) fn profile_index(&self, name: &str) -> Option<usize> {
+        self.profile_names().iter().position(|&n| n == name)
+    }
+
+    /// Resolves the `[pointers]` section against the game's base addresses.
+    pub(crate) fn user_pointers<M: MemoryBackend>(&self, chains: &Pointers<M>) -> UserPointers<M> {
+        self.pointers
+            .iter()
+            .filter_map(|(name, spec)| {
+                let chain = chains.numeric_chain(&spec.base, &spec.offsets, spec.value_type.0)?;
+                let label = spec.label.clone().unwrap_or_else(|| name.clone());
+                Some((name.clone(), UserPointer { label, chain, mask: spec.mask }))
+            })
+            .collect()
+    }
+
+    /// Builds the widgets of the profile at `profile`, as indexed in
+    /// [`Config::profile_names`].
+    pub(crate) fn make_commands<M: MemoryBackend>(
+        &self,
+        profile: usize,
+        ctx: &CommandContext<M>,
+    ) -> Vec<Box<dyn Widget>> {
+        let commands = match profile {
+            0 => &self.commands,
+            i => self.profiles.get(i - 1).map(|p| &p.commands).unwrap_or(&self.commands),
+        };
+        commands.iter().cloned().filter_map(|c| c.into_widget(self, ctx)).collect()
     }
 }
 
@@ -235,81 +1225,355 @@ impl Default for Config {
                 hide: "rshift+0".parse().ok(),
                 show_console: false,
                 indicators: Indicator::default_set(),
+                profile: None,
+                cycle_profile: None,
             },
+            pointers: BTreeMap::new(),
+            event_flag_presets: BTreeMap::new(),
             commands: Vec::new(),
+            profiles: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 }
 
-#[derive(Deserialize)]
+/// Writes the settings that can be changed at runtime, i.e. which
+/// indicators are enabled, into `cfg`, the source of the config file.
+/// Everything else, including comments and the order of the commands, is
+/// kept as it is.
+pub(crate) fn save_settings(cfg: &str, settings: &Settings) -> Result<String, String> {
+    let mut document = cfg
+        .parse::<toml_edit::Document>()
+        .map_err(|e| format!("Couldn't parse config file: {e}"))?;
+    let settings_table = document
+        .get_mut("settings")
+        .and_then(|s| s.as_table_like_mut())
+        .ok_or_else(|| "Couldn't find [settings] in config file".to_string())?;
+
+    if let Some(profile) = &settings.profile {
+        match settings_table.get_mut("profile") {
+            Some(toml_edit::Item::Value(value)) => {
+                let decor = value.decor().clone();
+                *value = profile.into();
+                *value.decor_mut() = decor;
+            },
+            _ => {
+                settings_table.insert("profile", toml_edit::value(profile));
+            },
+        }
+    }
+
+    let indicators = settings.indicators.iter().map(|i| (i.indicator.name(), i.enabled));
+
+    let entries: Vec<&mut dyn toml_edit::TableLike> = match settings_table.get_mut("indicators") {
+        Some(toml_edit::Item::Value(toml_edit::Value::Array(array))) => array
+            .iter_mut()
+            .filter_map(|v| v.as_inline_table_mut())
+            .map(|t| t as &mut dyn toml_edit::TableLike)
+            .collect(),
+        Some(toml_edit::Item::ArrayOfTables(tables)) => {
+            tables.iter_mut().map(|t| t as &mut dyn toml_edit::TableLike).collect()
+        },
+        Some(_) => return Err("Couldn't read indicators in config file".to_string()),
+        None => {
+            // The defaults are in use: write them out.
+            let mut array = toml_edit::Array::new();
+            for (name, enabled) in indicators {
+                let mut table = toml_edit::InlineTable::new();
+                table.insert("indicator", name.into());
+                table.insert("enabled", enabled.into());
+                array.push(table);
+            }
+            array.iter_mut().for_each(|v| v.decor_mut().set_prefix("\n  "));
+            array.set_trailing("\n");
+            array.set_trailing_comma(true);
+            settings_table.insert("indicators", toml_edit::value(array));
+            return Ok(document.to_string());
+        },
+    };
+
+    let enabled = indicators.collect::<BTreeMap<_, _>>();
+    for entry in entries {
+        let name = entry.get("indicator").and_then(|i| i.as_str());
+        let Some(&enabled) = name.and_then(|name| enabled.get(name)) else { continue };
+        match entry.get_mut("enabled") {
+            Some(toml_edit::Item::Value(value)) => {
+                let decor = value.decor().clone();
+                *value = enabled.into();
+                *value.decor_mut() = decor;
+            },
+            _ => {
+                entry.insert("enabled", toml_edit::value(enabled));
+            },
+        }
+    }
+
+    Ok(document.to_string())
+}
+
+#[derive(Debug, Deserialize, Clone)]
 #[serde(try_from = "String")]
 struct FlagSpec {
     label: String,
-    getter: fn(&Pointers) -> &Bitflag<u8>,
+    name: String,
 }
 
-impl std::fmt::Debug for FlagSpec {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "FlagSpec {{ label: {:?} }}", self.label)
+impl FlagSpec {
+    fn new(label: &str, name: &str) -> FlagSpec {
+        FlagSpec { label: label.to_string(), name: name.to_string() }
     }
 }
 
-impl FlagSpec {
-    fn new(label: &str, getter: fn(&Pointers) -> &Bitflag<u8>) -> FlagSpec {
-        FlagSpec { label: label.to_string(), getter }
+macro_rules! flag_spec {
+    (
+        [ $( ($flag_name:ident, $flag_label:expr), )* ],
+        chr: [ $( ($chr_flag_name:ident, $chr_flag_label:expr), )* ]
+    ) => {
+        impl TryFrom<String> for FlagSpec {
+            type Error = String;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                match value.as_str() {
+                    $(stringify!($flag_name) => {
+                        Ok(FlagSpec::new($flag_label, stringify!($flag_name)))
+                    },)*
+                    $(stringify!($chr_flag_name) => {
+                        Ok(FlagSpec::new($chr_flag_label, stringify!($chr_flag_name)))
+                    },)*
+                    // Validated against the `[pointers]` section in `Config::validate`.
+                    e => Ok(FlagSpec::new(e, e)),
+                }
+            }
+        }
+
+        impl FlagSpec {
+            const NAMES: &'static [&'static str] =
+                &[$(stringify!($flag_name),)* $(stringify!($chr_flag_name),)*];
+
+            /// Whether the flag is built-in or a masked user-declared pointer.
+            fn is_valid(&self, pointers: &BTreeMap<String, PointerSpec>) -> bool {
+                Self::NAMES.contains(&self.name.as_str())
+                    || pointers.get(&self.name).map(|p| p.mask.is_some()).unwrap_or(false)
+            }
+
+            /// Returns the built-in flag, or `None` for user-declared ones and
+            /// built-in ones which aren't available on the running game
+            /// version.
+            fn get<'a, M: MemoryBackend>(
+                &self,
+                chains: &'a Pointers<M>,
+            ) -> Option<&'a Bitflag<u8, M>> {
+                match self.name.as_str() {
+                    $(stringify!($flag_name) => Some(&chains.$flag_name),)*
+                    $(stringify!($chr_flag_name) => {
+                        chains.chr.as_ref().map(|chr| &chr.$chr_flag_name)
+                    },)*
+                    _ => None,
+                }
+            }
+        }
     }
 }
 
-impl TryFrom<String> for FlagSpec {
+flag_spec!([
+    (render_world, "Render World"),
+    (render_objects, "Render Objects"),
+    (render_mobs, "Render Mobs"),
+    (render_effects, "Render Effects"),
+    (debug_render0, "Debug #0 (Low Col + Planes)"),
+    (debug_render1, "Debug #1 (High Col)"),
+    (debug_render2, "Debug #2 (Objects)"),
+    (debug_render3, "Debug #3 (Low Col?)"),
+    (debug_render4, "Debug #4 (Low Col?)"),
+    (debug_render5, "Debug #5 (Walls?)"),
+    (debug_render6, "Debug #6 (Wall Jump Col)"),
+    (debug_render7, "Debug #7 (Edge/Cliff Col)"),
+    (debug_show, "Debug Show"),
+    (grapple_debug_path, "Grapple Debug (Path)"),
+    (grapple_debug_col, "Grapple Debug (Col)"),
+    (player_no_goods_consume, "No goods consume"),
+    (player_no_resource_item_consume, "No resource consume"),
+    (player_no_revival_consume, "No revival consume"),
+    (player_hide, "Hide"),
+    (player_silence, "Silence"),
+    (player_no_dead, "No Dead"),
+    (player_exterminate, "Exterminate"),
+    (player_exterminate_stamina, "Exterminate Stamina"),
+    (all_no_dead, "All No Dead"),
+    (all_no_damage, "All No Damage"),
+    (all_no_hit, "All No Hit"),
+    (all_no_attack, "All No Attack"),
+    (all_no_move, "All No Move"),
+    (all_no_update_ai, "All No Update AI"),
+    (all_no_stamina_consume, "All No Stamina Consume"),
+], chr: [
+    (no_collision, "No Collision"),
+    (no_gravity, "No Gravity"),
+]);
+
+/// Name of a numeric value: either a built-in one or a pointer declared
+/// in the `[pointers]` section.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(transparent)]
+struct ValueSpec(String);
+
+impl ValueSpec {
+    const NAMES: &'static [&'static str] = &[
+        "anim_speed",
+        "game_speed",
+        "position_x",
+        "position_y",
+        "position_z",
+        "igt",
+        "fps",
+        "debug_color",
+        "hp",
+        "max_hp",
+        "posture",
+        "max_posture",
+        "resurrections",
+        "target_hp",
+        "target_max_hp",
+        "target_posture",
+        "target_max_posture",
+    ];
+
+    fn is_valid(&self, pointers: &BTreeMap<String, PointerSpec>) -> bool {
+        Self::NAMES.contains(&self.0.as_str()) || pointers.contains_key(&self.0)
+    }
+
+    /// Returns the label and chain of the value, or `None` if it isn't
+    /// available on the running game version.
+    fn resolve<M: MemoryBackend>(
+        &self,
+        chains: &Pointers<M>,
+        user_pointers: &UserPointers<M>,
+    ) -> Option<(String, NumericChain<M>)> {
+        let (label, chain): (&str, NumericChain<M>) = match self.0.as_str() {
+            "anim_speed" => ("Speed", chains.anim_speed.clone().into()),
+            "game_speed" => ("Game Speed", chains.game_speed.clone().into()),
+            "position_x" => ("Position X", chains.position.field::<f32>(0).into()),
+            "position_y" => ("Position Y", chains.position.field::<f32>(4).into()),
+            "position_z" => ("Position Z", chains.position.field::<f32>(8).into()),
+            "igt" => ("IGT", chains.igt.clone().into()),
+            "fps" => ("FPS", chains.fps.clone().into()),
+            "debug_color" => ("Debug Color", chains.debug_color.clone().into()),
+            "hp" => ("HP", chains.chr.as_ref()?.hp.clone().into()),
+            "max_hp" => ("Max HP", chains.chr.as_ref()?.max_hp.clone().into()),
+            "posture" => ("Posture", chains.chr.as_ref()?.posture.clone().into()),
+            "max_posture" => ("Max Posture", chains.chr.as_ref()?.max_posture.clone().into()),
+            "resurrections" => ("Resurrections", chains.chr.as_ref()?.resurrections.clone().into()),
+            "target_hp" => ("Target HP", chains.chr.as_ref()?.target_hp.clone().into()),
+            "target_max_hp" => ("Target Max HP", chains.chr.as_ref()?.target_max_hp.clone().into()),
+            "target_posture" => {
+                ("Target Posture", chains.chr.as_ref()?.target_posture.clone().into())
+            },
+            "target_max_posture" => {
+                ("Target Max Posture", chains.chr.as_ref()?.target_max_posture.clone().into())
+            },
+            name => {
+                let pointer = user_pointers.get(name)?;
+                return Some((pointer.label.clone(), pointer.chain.clone()));
+            },
+        };
+        Some((label.to_string(), chain))
+    }
+}
+
+/// A value captured by a snapshot: the whole position vector, or a
+/// numeric value.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(from = "String")]
+enum SnapshotValue {
+    Position,
+    Value(ValueSpec),
+}
+
+impl From<String> for SnapshotValue {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "position" => SnapshotValue::Position,
+            _ => SnapshotValue::Value(ValueSpec(value)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(try_from = "String")]
+enum RefillSpec {
+    Hp,
+    Posture,
+    TargetHp,
+    TargetPosture,
+}
+
+impl TryFrom<String> for RefillSpec {
     type Error = String;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        macro_rules! flag_spec {
-            ($x:expr, [ $( ($flag_name:ident, $flag_label:expr), )* ]) => {
-                match $x {
-                    $(stringify!($flag_name) => Ok(FlagSpec::new($flag_label, |c| &c.$flag_name)),)*
-                    e => Err(format!("\"{}\" is not a valid flag specifier", e)),
-                }
-            }
+        match value.as_str() {
+            "hp" => Ok(RefillSpec::Hp),
+            "posture" => Ok(RefillSpec::Posture),
+            "target_hp" => Ok(RefillSpec::TargetHp),
+            "target_posture" => Ok(RefillSpec::TargetPosture),
+            e => Err(format!("\"{}\" is not a valid refill specifier", e)),
         }
-        flag_spec!(value.as_str(), [
-            (render_world, "Render World"),
-            (render_objects, "Render Objects"),
-            (render_mobs, "Render Mobs"),
-            (render_effects, "Render Effects"),
-            (debug_render0, "Debug #0 (Low Col + Planes)"),
-            (debug_render1, "Debug #1 (High Col)"),
-            (debug_render2, "Debug #2 (Objects)"),
-            (debug_render3, "Debug #3 (Low Col?)"),
-            (debug_render4, "Debug #4 (Low Col?)"),
-            (debug_render5, "Debug #5 (Walls?)"),
-            (debug_render6, "Debug #6 (Wall Jump Col)"),
-            (debug_render7, "Debug #7 (Edge/Cliff Col)"),
-            (debug_show, "Debug Show"),
-            (grapple_debug_path, "Grapple Debug (Path)"),
-            (grapple_debug_col, "Grapple Debug (Col)"),
-            (player_no_goods_consume, "No goods consume"),
-            (player_no_resource_item_consume, "No resource consume"),
-            (player_no_revival_consume, "No revival consume"),
-            (player_hide, "Hide"),
-            (player_silence, "Silence"),
-            (player_no_dead, "No Dead"),
-            (player_exterminate, "Exterminate"),
-            (player_exterminate_stamina, "Exterminate Stamina"),
-            (all_no_dead, "All No Dead"),
-            (all_no_damage, "All No Damage"),
-            (all_no_hit, "All No Hit"),
-            (all_no_attack, "All No Attack"),
-            (all_no_move, "All No Move"),
-            (all_no_update_ai, "All No Update AI"),
-            (all_no_stamina_consume, "All No Stamina Consume"),
-        ])
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use libsekiro::prelude::*;
+    use practice_tool_core::widgets::Widget;
+
+    use super::{save_settings, CommandContext, Config, FlagSpec, ValueSpec};
+
+    /// Everything a `CommandContext` borrows, over fake memory.
+    struct TestContext {
+        memory: FakeMemory,
+        pointers: Pointers<FakeMemory>,
+        freezer: Freezer,
+        event_flags: EventFlags<FakeMemory>,
+        warp: Warp<FakeMemory>,
+        frame_advance: FrameAdvance<FakeMemory>,
+        camera: CameraPointers<FakeMemory>,
+    }
+
+    impl TestContext {
+        fn make_commands(&self, config: &Config, profile: usize) -> Vec<Box<dyn Widget>> {
+            let user_pointers = config.user_pointers(&self.pointers);
+            config.make_commands(profile, &CommandContext {
+                chains: &self.pointers,
+                user_pointers: &user_pointers,
+                freezer: &self.freezer,
+                event_flags: &self.event_flags,
+                warp: &self.warp,
+                frame_advance: &self.frame_advance,
+                camera: &self.camera,
+            })
+        }
+    }
+
+    fn test_context() -> TestContext {
+        test_context_for(base_addresses::BASE_ADDRESSES_1_06_0, Version::V1_06_0)
+    }
+
+    fn test_context_for(
+        base_addresses: base_addresses::BaseAddresses,
+        version: Version,
+    ) -> TestContext {
+        let memory = FakeMemory::new();
+        let pointers = Pointers::with_backend(memory.clone(), base_addresses, version);
+        TestContext {
+            freezer: Freezer::new(),
+            event_flags: EventFlags::with_backend(memory.clone(), 0),
+            warp: Warp::with_backend(memory.clone(), 0),
+            frame_advance: FrameAdvance::new(pointers.game_speed.clone()),
+            camera: CameraPointers::with_backend(memory.clone(), 0),
+            memory,
+            pointers,
+        }
+    }
 
     #[test]
     fn test_parse() {
@@ -332,4 +1596,297 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_error_locations() {
+        let config = |commands: &str| {
+            Config::parse(&format!(
+                "commands = [\n  {commands}\n]\n[settings]\nlog_level = \"DEBUG\"\ndisplay = \
+                 \"0\"\n"
+            ))
+        };
+        let parse = |commands: &str| config(commands).unwrap_err();
+        let warnings = |commands: &str| config(commands).unwrap().warnings().to_vec();
+
+        assert_eq!(
+            parse(r#"{ flga = "all_no_dead" }"#),
+            "TOML configuration parse error: commands[0] (line 2, column 5): unknown command \
+             \"flga\"; did you mean \"flag\"?"
+        );
+        assert_eq!(
+            parse(r#"{ hotkey = "1", vlag = "all_no_dead" }"#),
+            "TOML configuration parse error: commands[0] (line 2, column 19): unknown command \
+             \"vlag\"; did you mean \"flag\"?"
+        );
+        assert_eq!(warnings(r#"{ flag = "all_no_dead", hotkey = "1" }"#), Vec::<String>::new());
+        assert_eq!(warnings(r#"{ flag = "all_no_dead", hotkye = "1", lable = "x" }"#), [
+            "commands[0] (line 2, column 27): unknown key \"hotkye\" in \"flag\" command; did you \
+             mean \"hotkey\"?",
+            "commands[0] (line 2, column 41): unknown key \"lable\" in \"flag\" command",
+        ]);
+        assert_eq!(
+            warnings(r#"{ warp = true }, { group = "G", commands = [{ warp = true, idl = 1 }] }"#),
+            ["commands[1].commands[0] (line 2, column 62): unknown key \"idl\" in \"warp\" \
+              command; did you mean \"idol\"?"]
+        );
+        assert_eq!(
+            parse(r#"{ flag = "all_no_daed" }"#),
+            "Configuration error: commands[0] (line 2, column 3): \"all_no_daed\" is not a valid \
+             flag specifier; did you mean \"all_no_dead\"?"
+        );
+        assert_eq!(
+            parse(r#"{ edit_value = "hp" }, { edit_value = "hpp" }"#),
+            "Configuration error: commands[1] (line 2, column 26): \"hpp\" is not a valid value \
+             specifier; did you mean \"hp\"?"
+        );
+        assert!(parse(r#"{ cycle_speed = "fast" }"#).starts_with(
+            "TOML configuration parse error: commands[0] (line 2, column 3): data did not match"
+        ));
+
+        let error = Config::parse(
+            "commands = []\n[settings]\nlog_level = \"DEBUG\"\ndisplay = \"0\"\nindicators = [{ \
+             indicator = \"postion\", enabled = true }]",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            "Configuration error: Unrecognized indicator (line 5, column 15): \"postion\"; did \
+             you mean \"position\"?"
+        );
+    }
+
+    #[test]
+    fn test_make_commands() {
+        let config = Config::parse(
+            r#"commands = [
+                { flag = "all_no_dead", hotkey = "1" },
+                { cycle_speed = [0.5, 1.0], hotkey = "2" },
+                { group = "Positions", commands = [
+                    { nudge = 1.0, nudge_up = "[", nudge_down = "]" },
+                    { nudge = 0.5, nudge_forward = "i", nudge_back = "k", nudge_left = "j", nudge_right = "l" },
+                    { position = "h", save = "rshift+h" },
+                    { teleport = "t" },
+                    { position_library = "l" },
+                    { snapshot = "f5", save = "rshift+f5" },
+                    { snapshot = "f6", save = "rshift+f6", label = "Boss", values = ["position", "hp", "target_hp"], flags = ["all_no_damage"] },
+                ]},
+                { quitout = "p" },
+                { freeze = "anim_speed", value = 0.5, hotkey = "3" },
+                { freeze = "position_y" },
+                { set_value = "anim_speed", value = 2.0, hotkey = "4" },
+                { toggle_value = "debug_color", values = [0, 3], hotkey = "5" },
+                { edit_value = "position_y", min = -100.0, max = 100.0 },
+                { edit_value = "igt" },
+                { refill = "hp", hotkey = "6" },
+                { set_value = "posture", value = 1 },
+                { group = "Target", commands = [
+                    { set_value = "target_hp", value = 1 },
+                    { refill = "target_posture" },
+                ]},
+                { event_flag = 11105800, label = "Genichiro defeated" },
+                { event_flag_preset = "ashina_castle", hotkey = "7" },
+                { warp = "8", idol = 1111950 },
+                { warp = true },
+                { game_speed = [0.25, 0.5, 1.0], hotkey = "9" },
+                { frame_advance = "p", step = "o", step_n = "rshift+o", frames = 5 },
+                { free_cam = "f", return = "rshift+f", speed = 20.0 },
+                { no_clip = "n", speed = 5.0 },
+            ]
+            [settings]
+            log_level = "DEBUG"
+            display = "0"
+            [event_flag_presets]
+            ashina_castle = { label = "Ashina Castle", on = [11105800], off = [11105801] }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(test_context().make_commands(&config, 0).len(), 21);
+
+        // HP, posture and no clip aren't available before 1.05.
+        let ctx = test_context_for(base_addresses::BASE_ADDRESSES_1_04_0, Version::V1_04_0);
+        assert_eq!(ctx.make_commands(&config, 0).len(), 18);
+    }
+
+    #[test]
+    fn test_profiles() {
+        let cfg = r#"commands = [{ quitout = "p" }]
+
+            [settings]
+            log_level = "DEBUG"
+            display = "0"
+            profile = "Boss rush"
+            cycle_profile = "rshift+p"
+
+            [[profiles]]
+            name = "All Memories"
+            commands = [{ quitout = "p" }, { flag = "all_no_dead", hotkey = "1" }]
+
+            [[profiles]]
+            name = "Boss rush"
+            commands = [{ quitout = "p" }, { position = "r", save = "f" }, { warp = true }]
+            "#;
+        let config = Config::parse(cfg).unwrap();
+        assert_eq!(config.profile_names(), ["Default", "All Memories", "Boss rush"]);
+        assert_eq!(
+            config.settings.profile.as_deref().and_then(|p| config.profile_index(p)),
+            Some(2)
+        );
+
+        let ctx = test_context();
+        assert_eq!(ctx.make_commands(&config, 0).len(), 1);
+        assert_eq!(ctx.make_commands(&config, 1).len(), 2);
+        assert_eq!(ctx.make_commands(&config, 2).len(), 3);
+
+        assert_eq!(
+            Config::parse(
+                &cfg.replace(
+                    "\"Boss rush\"\n            cycle",
+                    "\"Boss rsuh\"\n            cycle"
+                )
+            )
+            .unwrap_err(),
+            "Configuration error: Unrecognized profile (line 6, column 13): \"Boss rsuh\"; did \
+             you mean \"Boss rush\"?"
+        );
+        assert_eq!(
+            Config::parse(&cfg.replace("\"All Memories\"", "\"Boss rush\"")).unwrap_err(),
+            "Configuration error: profiles[1] (line 14, column 13): profile name \"Boss rush\" is \
+             already in use"
+        );
+        assert_eq!(
+            Config::parse(&cfg.replace("hotkey = \"1\"", "hotkye = \"1\"")).unwrap().warnings(),
+            ["profiles[0].commands[1] (line 11, column 68): unknown key \"hotkye\" in \"flag\" \
+              command; did you mean \"hotkey\"?"]
+        );
+    }
+
+    #[test]
+    fn test_save_settings() {
+        let cfg = r#"# Commands
+commands = [
+  { quitout = "p" },
+  { position = "r", save = "f" },
+]
+
+# Settings
+[settings]
+log_level = "DEBUG" # Comment
+display = "0"
+indicators = [
+  { indicator = "igt", enabled = true },    # Aligned
+  { indicator = "fps", enabled = false },
+]
+"#;
+        let mut config = Config::parse(cfg).unwrap();
+        config.settings.indicators[0].enabled = false;
+        config.settings.indicators[1].enabled = true;
+
+        let saved = save_settings(cfg, &config.settings).unwrap();
+        assert_eq!(
+            saved,
+            cfg.replace("igt\", enabled = true", "igt\", enabled = false")
+                .replace("fps\", enabled = false", "fps\", enabled = true")
+        );
+
+        let cfg = "commands = []\n\n[settings]\nlog_level = \"DEBUG\"\ndisplay = \"0\"\n";
+        let mut config = Config::parse(cfg).unwrap();
+        config.settings.profile = Some("Default".to_string());
+        let saved = save_settings(cfg, &config.settings).unwrap();
+        let reparsed = Config::parse(&saved).unwrap();
+        assert_eq!(reparsed.settings.profile.as_deref(), Some("Default"));
+        assert_eq!(
+            reparsed.settings.indicators.iter().map(|i| i.enabled).collect::<Vec<_>>(),
+            config.settings.indicators.iter().map(|i| i.enabled).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_user_pointers() {
+        let ctx = test_context();
+        let (memory, pointers) = (&ctx.memory, &ctx.pointers);
+        let config = Config::parse(
+            r#"commands = [
+                { flag = "no_death", hotkey = "1" },
+                { freeze = "my_hp", value = 1000 },
+            ]
+            [settings]
+            log_level = "DEBUG"
+            display = "0"
+            indicators = [ { indicator = "my_hp", enabled = true } ]
+            [pointers]
+            my_hp = { base = "player_position", offsets = [0x48, 0x130], type = "i32", label = "HP" }
+            no_death = { base = "player_position", offsets = [0x48, 0x140], type = "u16", mask = 0x100 }
+            "#,
+        )
+        .unwrap();
+
+        let user_pointers = config.user_pointers(pointers);
+        assert_eq!(user_pointers["my_hp"].label, "HP");
+        assert_eq!(user_pointers["my_hp"].chain.value_type(), ValueType::I32);
+        assert_eq!(user_pointers["no_death"].label, "no_death");
+
+        let base = base_addresses::BASE_ADDRESSES_1_06_0.player_position;
+        memory.put(base, 0x1000usize);
+        memory.put(0x1048, 0x2000usize);
+        memory.put(0x2130, 850i32);
+        memory.put(0x2140, 0x0101u16);
+        assert_eq!(user_pointers["my_hp"].display(), Ok("850".to_string()));
+        assert_eq!(user_pointers["no_death"].display(), Ok("on".to_string()));
+
+        // The commands use the user-declared chains.
+        let (label, chain) =
+            ValueSpec("my_hp".to_string()).resolve(pointers, &user_pointers).unwrap();
+        assert_eq!(label, "HP");
+        assert_eq!(chain.read(), Ok(850.));
+        assert!(FlagSpec::try_from("no_death".to_string()).unwrap().get(pointers).is_none());
+
+        assert_eq!(ctx.make_commands(&config, 0).len(), 2);
+    }
+
+    #[test]
+    fn test_user_pointer_errors() {
+        let parse = |pointers: &str, commands: &str| {
+            Config::parse(&format!(
+                "commands = [ {commands} ]\n[settings]\nlog_level = \"DEBUG\"\ndisplay = \
+                 \"0\"\n[pointers]\n{pointers}"
+            ))
+        };
+
+        assert!(parse(r#"x = { base = "igt", type = "u32" }"#, r#"{ freeze = "x" }"#).is_ok());
+        assert!(parse(r#"x = { base = "nope", type = "u32" }"#, "").is_err());
+        assert!(parse(r#"x = { base = "igt", type = "u128" }"#, "").is_err());
+        assert!(parse(r#"x = { base = "igt", type = "f32", mask = 1 }"#, "").is_err());
+        assert!(parse(r#"x = { base = "igt", type = "i8", mask = 0x80 }"#, "").is_ok());
+        assert_eq!(
+            parse(r#"x = { base = "igt", type = "u8", mask = 0x100 }"#, "").unwrap_err(),
+            "Configuration error: pointer \"x\": mask 0x100 doesn't fit in u8"
+        );
+        assert_eq!(
+            parse(r#"hp = { base = "igt", type = "u32" }"#, "").unwrap_err(),
+            "Configuration error: pointer \"hp\" (line 6, column 1): the name is already used by \
+             a built-in value"
+        );
+        assert!(parse(r#"player_no_dead = { base = "igt", type = "u8", mask = 1 }"#, "").is_err());
+        assert!(parse(r#"target = { base = "igt", type = "u32" }"#, "").is_err());
+        assert!(parse(r#"x = { base = "igt", type = "u32" }"#, r#"{ flag = "x" }"#).is_err());
+        assert!(parse(r#"x = { base = "igt", type = "u32" }"#, r#"{ freeze = "y" }"#).is_err());
+        assert!(parse("", r#"{ flag = "not_a_flag" }"#).is_err());
+        assert!(parse(r#"x = { base = "igt", type = "u32" }"#, r#"{ edit_value = "x" }"#).is_ok());
+        assert!(parse("", r#"{ set_value = "nope", value = 1 }"#).is_err());
+        assert!(parse("", r#"{ edit_value = "igt", min = 0 }"#).is_err());
+        assert!(parse("", r#"{ event_flag_preset = "nope" }"#).is_err());
+        assert!(parse("", r#"{ warp = true, idol = 1 }"#).is_err());
+        assert!(parse(
+            r#"x = { base = "igt", type = "u8", mask = 1 }"#,
+            r#"{ snapshot = true, values = ["position", "x"] }"#
+        )
+        .is_ok());
+        assert!(parse("", r#"{ snapshot = true, values = ["nope"] }"#).is_err());
+        assert!(parse(
+            r#"x = { base = "igt", type = "u8", mask = 1 }"#,
+            r#"{ snapshot = true, flags = ["x"] }"#
+        )
+        .is_err());
+    }
 }
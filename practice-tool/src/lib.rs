@@ -15,27 +15,23 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 mod config;
+mod positions;
 mod practice_tool;
 mod widgets;
 
 use std::os::raw::c_void;
-use std::ptr::null_mut;
 use std::time::{Duration, Instant};
 use std::{mem, thread};
 
 use hudhook::hooks::dx11::ImguiDx11Hooks;
 use hudhook::tracing::{error, trace};
 use hudhook::*;
-use libsekiro::codegen::base_addresses;
-use libsekiro::version::VERSION;
+use libsekiro::pointers::CodePatches;
 use once_cell::sync::Lazy;
 use practice_tool::PracticeTool;
 use windows::core::{s, w, GUID, HRESULT, PCWSTR};
 use windows::Win32::Foundation::{HINSTANCE, MAX_PATH};
-use windows::Win32::System::LibraryLoader::{GetModuleHandleW, GetProcAddress, LoadLibraryW};
-use windows::Win32::System::Memory::{
-    VirtualProtect, PAGE_EXECUTE_READWRITE, PAGE_PROTECTION_FLAGS,
-};
+use windows::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryW};
 use windows::Win32::System::SystemInformation::GetSystemDirectoryW;
 use windows::Win32::System::SystemServices::DLL_PROCESS_ATTACH;
 use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_RSHIFT};
@@ -48,6 +44,8 @@ type FDirectInput8Create = unsafe extern "system" fn(
     punkouter: HINSTANCE,
 ) -> HRESULT;
 
+pub(crate) static PATCHES: Lazy<CodePatches> = Lazy::new(CodePatches::new);
+
 static DIRECTINPUT8CREATE: Lazy<FDirectInput8Create> = Lazy::new(|| unsafe {
     let mut dinput8_path = [0u16; MAX_PATH as usize];
     let count = GetSystemDirectoryW(Some(&mut dinput8_path)) as usize;
@@ -77,38 +75,29 @@ unsafe extern "system" fn DirectInput8Create(
     (DIRECTINPUT8CREATE)(hinst, dwversion, riidltf, ppvout, punkouter)
 }
 
-unsafe fn apply_no_logo() {
-    let module_base = GetModuleHandleW(PCWSTR(null_mut())).unwrap();
-    let offset = base_addresses::BaseAddresses::from(*VERSION).no_logo;
-
-    let ptr = (module_base.0 as usize + offset) as *mut [u8; 2];
-    let mut old = PAGE_PROTECTION_FLAGS(0);
-    if *ptr == [0x74, 0x30] {
-        VirtualProtect(ptr as _, 2, PAGE_EXECUTE_READWRITE, &mut old).ok();
-        (*ptr) = [0x75, 0x30];
-        VirtualProtect(ptr as _, 2, old, &mut old).ok();
+fn apply_no_logo() {
+    if let Err(e) = PATCHES.no_logo.apply() {
+        error!("{e}");
     }
 }
 
-// Credits to ElaDiDu for the font crash fix
-unsafe fn apply_font_patch() {
-    let module_base = GetModuleHandleW(PCWSTR(null_mut())).unwrap();
-    let offset = base_addresses::BaseAddresses::from(*VERSION).font_patch;
+fn apply_font_patch() {
+    if let Err(e) = PATCHES.font_patch.apply() {
+        error!("{e}");
+    }
+}
 
-    let ptr = (module_base.0 as usize + offset - 0x24) as *mut u8;
-    let mut old = PAGE_PROTECTION_FLAGS(0);
-    if *ptr == 0x48 && VirtualProtect(ptr as _, 1, PAGE_EXECUTE_READWRITE, &mut old).is_ok() {
-        (*ptr) = 0xC3;
-        VirtualProtect(ptr as _, 1, old, &mut old).ok();
+/// Restores the game's code as it was before the tool was injected.
+pub(crate) fn revert_patches() {
+    for e in PATCHES.revert_all() {
+        error!("{e}");
     }
 }
 
 fn start_practice_tool(hmodule: HINSTANCE) {
     let practice_tool = PracticeTool::new();
 
-    unsafe {
-        apply_font_patch();
-    }
+    apply_font_patch();
 
     if let Err(e) = Hudhook::builder()
         .with::<ImguiDx11Hooks>(practice_tool)
@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::util;
+
+const LIBRARY_FILE_NAME: &str = "jdsd_sekiro_practice_tool_positions.toml";
+
+/// A named position in the library.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct SavedPosition {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) area: String,
+    #[serde(default)]
+    pub(crate) notes: String,
+    pub(crate) position: [f32; 4],
+}
+
+/// Layout of the library file and of exported position packs.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct PositionFile {
+    #[serde(default)]
+    positions: Vec<SavedPosition>,
+}
+
+/// Named positions persisted to a file next to the DLL, so they survive
+/// game restarts and can be shared as position packs.
+#[derive(Debug, Default)]
+pub(crate) struct PositionLibrary {
+    path: Option<PathBuf>,
+    positions: Vec<SavedPosition>,
+    load_error: Option<String>,
+}
+
+impl PositionLibrary {
+    /// Loads the library from its file next to the DLL. A missing file is
+    /// an empty library.
+    pub(crate) fn load() -> Self {
+        match pack_path(LIBRARY_FILE_NAME) {
+            Some(path) => Self::load_from(path),
+            None => Self {
+                load_error: Some("Couldn't find position library file".to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Loads the library from `path`. If the file exists but can't be
+    /// read, the library starts empty and refuses to save, so that the
+    /// file isn't overwritten before the user can fix it.
+    fn load_from(path: PathBuf) -> Self {
+        let result = if path.exists() { read_positions(&path) } else { Ok(Vec::new()) };
+        let (positions, load_error) = match result {
+            Ok(positions) => (positions, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        Self { path: Some(path), positions, load_error }
+    }
+
+    /// Why the library file couldn't be loaded, if it couldn't.
+    pub(crate) fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+
+    pub(crate) fn positions(&self) -> &[SavedPosition] {
+        &self.positions
+    }
+
+    /// Indices of the positions, grouped by area and sorted by name.
+    pub(crate) fn by_area(&self) -> BTreeMap<&str, Vec<usize>> {
+        let mut areas = BTreeMap::<&str, Vec<usize>>::new();
+        for (i, position) in self.positions.iter().enumerate() {
+            areas.entry(position.area.as_str()).or_default().push(i);
+        }
+        for indices in areas.values_mut() {
+            indices.sort_by_key(|&i| self.positions[i].name.to_lowercase());
+        }
+        areas
+    }
+
+    /// Adds a position and returns its index.
+    pub(crate) fn add(&mut self, position: SavedPosition) -> Result<usize, String> {
+        self.positions.push(position);
+        self.save()?;
+        Ok(self.positions.len() - 1)
+    }
+
+    /// Replaces the name, area and notes of the position at `index`.
+    pub(crate) fn rename(
+        &mut self,
+        index: usize,
+        name: &str,
+        area: &str,
+        notes: &str,
+    ) -> Result<(), String> {
+        let position = &mut self.positions[index];
+        position.name = name.to_string();
+        position.area = area.to_string();
+        position.notes = notes.to_string();
+        self.save()
+    }
+
+    pub(crate) fn delete(&mut self, index: usize) -> Result<(), String> {
+        self.positions.remove(index);
+        self.save()
+    }
+
+    /// Adds the positions of a pack, skipping those with the same name and
+    /// area as one already in the library. Returns how many were added.
+    pub(crate) fn import(&mut self, path: &Path) -> Result<usize, String> {
+        let count = self.merge(read_positions(path)?);
+        self.save()?;
+        Ok(count)
+    }
+
+    /// Writes the whole library to a pack at `path`.
+    pub(crate) fn export(&self, path: &Path) -> Result<(), String> {
+        write_positions(path, &self.positions)
+    }
+
+    fn merge(&mut self, positions: Vec<SavedPosition>) -> usize {
+        let len = self.positions.len();
+        for position in positions {
+            if !self.positions.iter().any(|p| p.name == position.name && p.area == position.area) {
+                self.positions.push(position);
+            }
+        }
+        self.positions.len() - len
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(e) = &self.load_error {
+            return Err(format!("{e}\nNot saving: fix or remove the file first"));
+        }
+        match &self.path {
+            Some(path) => write_positions(path, &self.positions),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Resolves a pack file name relative to the DLL's directory.
+pub(crate) fn pack_path(name: &str) -> Option<PathBuf> {
+    util::get_dll_path().map(|mut path| {
+        path.pop();
+        path.push(name);
+        path
+    })
+}
+
+fn read_positions(path: &Path) -> Result<Vec<SavedPosition>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+    parse_positions(&content).map_err(|e| format!("Couldn't parse {}: {e}", path.display()))
+}
+
+fn write_positions(path: &Path, positions: &[SavedPosition]) -> Result<(), String> {
+    let content = serialize_positions(positions)?;
+    std::fs::write(path, content).map_err(|e| format!("Couldn't write {}: {e}", path.display()))
+}
+
+fn parse_positions(content: &str) -> Result<Vec<SavedPosition>, String> {
+    toml::from_str::<PositionFile>(content).map(|f| f.positions).map_err(|e| format!("{e}"))
+}
+
+fn serialize_positions(positions: &[SavedPosition]) -> Result<String, String> {
+    toml::to_string_pretty(&PositionFile { positions: positions.to_vec() })
+        .map_err(|e| format!("Couldn't serialize positions: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(name: &str, area: &str) -> SavedPosition {
+        SavedPosition {
+            name: name.to_string(),
+            area: area.to_string(),
+            notes: String::new(),
+            position: [1.5, -2., 3.25, 0.5],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut positions = vec![position("Genichiro", "Ashina Castle"), position("Gate", "")];
+        positions[0].notes = "Top of the castle\nafter the cutscene".to_string();
+
+        let content = serialize_positions(&positions).unwrap();
+        assert_eq!(parse_positions(&content), Ok(positions));
+
+        assert_eq!(
+            parse_positions("[[positions]]\nname = \"Gate\"\nposition = [1.5, -2.0, 3.25, 0.5]"),
+            Ok(vec![position("Gate", "")])
+        );
+        assert!(parse_positions("[[positions]]\nname = \"Gate\"").is_err());
+    }
+
+    #[test]
+    fn test_library() {
+        let mut library = PositionLibrary::default();
+        library.add(position("b", "Ashina Castle")).unwrap();
+        library.add(position("Gate", "")).unwrap();
+        library.add(position("A", "Ashina Castle")).unwrap();
+
+        let areas = library.by_area();
+        assert_eq!(areas.keys().copied().collect::<Vec<_>>(), ["", "Ashina Castle"]);
+        assert_eq!(areas["Ashina Castle"], [2, 0]);
+
+        let added = library.merge(vec![position("A", "Ashina Castle"), position("A", "")]);
+        assert_eq!(added, 1);
+        assert_eq!(library.positions().len(), 4);
+
+        library.rename(3, "Outskirts", "Ashina Outskirts", "").unwrap();
+        library.delete(0).unwrap();
+        assert_eq!(library.positions()[2].area, "Ashina Outskirts");
+        assert_eq!(library.positions().len(), 3);
+    }
+
+    #[test]
+    fn test_load_save() {
+        let path = std::env::temp_dir()
+            .join(format!("sekiro_practice_tool_positions_{}.toml", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let mut library = PositionLibrary::load_from(path.clone());
+        assert_eq!(library.load_error(), None);
+        library.add(position("Gate", "Ashina Outskirts")).unwrap();
+        let library = PositionLibrary::load_from(path.clone());
+        assert_eq!(library.positions(), [position("Gate", "Ashina Outskirts")]);
+
+        // A broken file is left alone.
+        std::fs::write(&path, "[[positions]]\nname = ").unwrap();
+        let mut library = PositionLibrary::load_from(path.clone());
+        assert!(library.load_error().is_some());
+        assert!(library.add(position("Gate", "")).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[[positions]]\nname = ");
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -1,21 +1,30 @@
 use std::fmt::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use const_format::formatcp;
 use hudhook::imgui::*;
+use hudhook::tracing::field::{Field, Visit};
 use hudhook::tracing::metadata::LevelFilter;
-use hudhook::tracing::{error, info};
+use hudhook::tracing::{error, info, warn, Event, Level, Subscriber};
 use hudhook::{ImguiRenderLoop, RenderContext};
-use libsekiro::pointers::Pointers;
+use libsekiro::event_flags::EventFlags;
+use libsekiro::frame_advance::FrameAdvance;
+use libsekiro::freeze::Freezer;
+use libsekiro::pointers::{CameraPointers, ChrPointers, Pointers};
+use libsekiro::scan::CURRENT_BASE_ADDRESSES;
 use libsekiro::version;
+use libsekiro::warp::Warp;
 use pkg_version::*;
 use practice_tool_core::crossbeam_channel::{self, Receiver, Sender};
 use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
+use tracing_subscriber::layer::{Context as LayerContext, Layer};
 use tracing_subscriber::prelude::*;
 
-use crate::config::{Config, IndicatorType, Settings};
+use crate::config::{self, CommandContext, Config, IndicatorType, Settings, UserPointers};
 use crate::util;
+use crate::widgets::ChainErrors;
 
 const MAJOR: usize = pkg_version_major!();
 const MINOR: usize = pkg_version_minor!();
@@ -30,6 +39,92 @@ struct FontIDs {
 unsafe impl Send for FontIDs {}
 unsafe impl Sync for FontIDs {}
 
+/// Forwards warnings and errors to the log window, so that failures which
+/// would otherwise only end up in the log file are visible in game.
+struct ErrorLogLayer(Sender<String>);
+
+impl<S: Subscriber> Layer<S> for ErrorLogLayer {
+    fn on_event(&self, event: &Event<'_>, _: LayerContext<'_, S>) {
+        struct MessageVisitor<'a>(&'a mut String);
+
+        impl Visit for MessageVisitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    write!(self.0, "{value:?}").ok();
+                }
+            }
+        }
+
+        if *event.metadata().level() <= Level::WARN {
+            let mut message = String::new();
+            event.record(&mut MessageVisitor(&mut message));
+            self.0.send(message).ok();
+        }
+    }
+}
+
+const CONFIG_FILE_NAME: &str = "jdsd_sekiro_practice_tool.toml";
+
+/// How often the config file is checked for changes.
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Polls the modification time of the config file.
+struct ConfigWatch {
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    last_poll: Instant,
+}
+
+impl ConfigWatch {
+    fn new() -> Self {
+        let path = util::get_dll_path().map(|mut path| {
+            path.pop();
+            path.push(CONFIG_FILE_NAME);
+            path
+        });
+        let mut watch = ConfigWatch { path, modified: None, last_poll: Instant::now() };
+        watch.modified = watch.modified();
+        watch
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        self.path.as_ref().and_then(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+    }
+
+    /// Whether the file was modified since the last call.
+    fn changed(&mut self) -> bool {
+        if self.last_poll.elapsed() < CONFIG_POLL_INTERVAL {
+            return false;
+        }
+        self.last_poll = Instant::now();
+
+        let modified = self.modified();
+        if modified.is_some() && modified != self.modified {
+            self.modified = modified;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Records the current modification time, so that a file written by
+    /// the tool itself isn't reloaded.
+    fn mark_saved(&mut self) {
+        self.modified = self.modified();
+    }
+}
+
+fn load_config(path: Option<&Path>) -> Result<Config, String> {
+    let path = path.ok_or_else(|| "Couldn't find config file".to_string())?;
+    let config_content =
+        std::fs::read_to_string(path).map_err(|e| format!("Couldn't read config file: {}", e))?;
+    Config::parse(&config_content)
+}
+
+fn config_error(e: &str) -> String {
+    format!("Configuration error, please review your {CONFIG_FILE_NAME} file.\n\n{e}")
+}
+
 enum UiState {
     MenuOpen,
     Closed,
@@ -38,13 +133,23 @@ enum UiState {
 
 pub struct PracticeTool {
     pointers: Pointers,
+    user_pointers: UserPointers,
+    freezer: Freezer,
+    event_flags: EventFlags,
+    warp: Warp,
+    frame_advance: FrameAdvance,
+    frame_advance_errors: ChainErrors,
+    camera: CameraPointers,
     settings: Settings,
+    config: Config,
+    profile: usize,
     version_label: String,
     widgets: Vec<Box<dyn Widget>>,
 
     log: Vec<(Instant, String)>,
     log_rx: Receiver<String>,
     log_tx: Sender<String>,
+    error_rx: Receiver<String>,
     ui_state: UiState,
     fonts: Option<FontIDs>,
 
@@ -56,9 +161,16 @@ pub struct PracticeTool {
 
     fps_buf: String,
 
+    hp_buf: String,
+    posture_buf: String,
+    target_bufs: [String; 3],
+
+    pointer_buf: String,
+
     framecount: u32,
     framecount_buf: String,
 
+    config_watch: ConfigWatch,
     config_err: Option<String>,
 }
 
@@ -67,32 +179,13 @@ impl PracticeTool {
         hudhook::alloc_console().ok();
         hudhook::enable_console_colors();
 
-        fn load_config() -> Result<Config, String> {
-            let config_path = util::get_dll_path()
-                .map(|mut path| {
-                    path.pop();
-                    path.push("jdsd_sekiro_practice_tool.toml");
-                    path
-                })
-                .ok_or_else(|| "Couldn't find config file".to_string())?;
-            let config_content = std::fs::read_to_string(config_path)
-                .map_err(|e| format!("Couldn't read config file: {}", e))?;
-            println!("{}", config_content);
-            Config::parse(&config_content)
-        }
-
-        let (config, config_err) = match load_config() {
+        let config_watch = ConfigWatch::new();
+        let (config, config_err) = match load_config(config_watch.path.as_deref()) {
             Ok(config) => (config, None),
-            Err(e) => (
-                Config::default(),
-                Some({
-                    error!("{}", e);
-                    format!(
-                        "Configuration error, please review your jdsd_er_practice_tool.toml \
-                         file.\n\n{e}"
-                    )
-                }),
-            ),
+            Err(e) => {
+                error!("{}", e);
+                (Config::default(), Some(config_error(&e)))
+            },
         };
 
         let log_file = util::get_dll_path()
@@ -109,6 +202,9 @@ impl PracticeTool {
             hudhook::free_console().ok();
         }
 
+        let (error_tx, error_rx) = crossbeam_channel::unbounded();
+        let error_layer = ErrorLogLayer(error_tx);
+
         match log_file {
             Some(Ok(log_file)) => {
                 let file_layer = tracing_subscriber::fmt::layer()
@@ -131,44 +227,182 @@ impl PracticeTool {
                     .with(log_level)
                     .with(file_layer)
                     .with(stdout_layer)
+                    .with(error_layer)
                     .init();
             },
-            e => match e {
-                None => error!("Could not construct log file path"),
-                Some(Err(e)) => error!("Could not initialize log file: {:?}", e),
-                _ => unreachable!(),
+            e => {
+                tracing_subscriber::registry().with(log_level).with(error_layer).init();
+
+                match e {
+                    None => error!("Could not construct log file path"),
+                    Some(Err(e)) => error!("Could not initialize log file: {:?}", e),
+                    _ => unreachable!(),
+                }
             },
         }
 
         let pointers = Pointers::new();
         let settings = config.settings.clone();
-        let widgets = config.make_commands(&pointers);
+        let frame_advance = FrameAdvance::new(pointers.game_speed.clone());
 
-        let version_label = {
-            let (maj, min, patch) = version::get_version().into();
-            format!("Game Ver {}.{:02}.{}", maj, min, patch)
+        let version_label = match (*version::VERSION, *version::FILE_VERSION) {
+            (Some(version), _) => {
+                let (maj, min, patch) = version.into();
+                format!("Game Ver {}.{:02}.{}", maj, min, patch)
+            },
+            (None, Some((maj, min, patch))) => {
+                format!("Game Ver {}.{:02}.{} (unknown)", maj, min, patch)
+            },
+            (None, None) => "Game Ver unknown".to_string(),
         };
+
+        let missing = CURRENT_BASE_ADDRESSES.missing();
+        if !missing.is_empty() {
+            error!(
+                "Base addresses not found: features relying on {} are disabled",
+                missing.join(", ")
+            );
+        } else if version::VERSION.is_none() {
+            info!("Unknown game version: all base addresses were found by scanning");
+        }
         let (log_tx, log_rx) = crossbeam_channel::unbounded();
         info!("Practice tool initialized");
 
-        PracticeTool {
+        let mut tool = PracticeTool {
             pointers,
+            user_pointers: Default::default(),
+            freezer: Freezer::new(),
+            event_flags: EventFlags::new(),
+            warp: Warp::new(),
+            frame_advance,
+            frame_advance_errors: Default::default(),
+            camera: CameraPointers::new(),
             settings,
+            config: Config::default(),
+            profile: 0,
             version_label,
-            widgets,
+            widgets: Vec::new(),
+            config_watch,
             ui_state: UiState::Closed,
             log: Default::default(),
             fonts: None,
             config_err,
             log_rx,
             log_tx,
+            error_rx,
             position_prev: Default::default(),
             position_bufs: Default::default(),
             position_change_buf: Default::default(),
             igt_buf: Default::default(),
             fps_buf: Default::default(),
+            hp_buf: Default::default(),
+            posture_buf: Default::default(),
+            target_bufs: Default::default(),
+            pointer_buf: Default::default(),
             framecount: 0,
             framecount_buf: Default::default(),
+        };
+        tool.apply_config(config);
+        tool
+    }
+
+    /// Replaces the settings and rebuilds the widgets out of `config`. The
+    /// profile in use is kept if `config` still has it.
+    fn apply_config(&mut self, config: Config) {
+        for warning in config.warnings() {
+            warn!("Config warning: {warning}");
+        }
+        let current = self.settings.profile.take().filter(|p| config.profile_index(p).is_some());
+        self.settings = config.settings.clone();
+        if current.is_some() {
+            self.settings.profile = current;
+        }
+        self.profile =
+            self.settings.profile.as_deref().and_then(|p| config.profile_index(p)).unwrap_or(0);
+        self.user_pointers = config.user_pointers(&self.pointers);
+        self.config = config;
+        self.make_widgets();
+    }
+
+    /// Switches to the profile at `index` and rebuilds the widgets.
+    fn select_profile(&mut self, index: usize) {
+        if index == self.profile {
+            return;
+        }
+        // Values frozen by commands of the previous profile couldn't be
+        // unfrozen anymore.
+        self.freezer.unfreeze_all();
+        self.profile = index;
+        let name = self.config.profile_names()[index].to_string();
+        self.log_tx.send(format!("Profile: {name}")).ok();
+        self.settings.profile = Some(name);
+        self.make_widgets();
+    }
+
+    fn make_widgets(&mut self) {
+        self.widgets = self.config.make_commands(self.profile, &CommandContext {
+            chains: &self.pointers,
+            user_pointers: &self.user_pointers,
+            freezer: &self.freezer,
+            event_flags: &self.event_flags,
+            warp: &self.warp,
+            frame_advance: &self.frame_advance,
+            camera: &self.camera,
+        });
+    }
+
+    /// Reloads the config file if it changed. An invalid file is reported
+    /// in the config error banner and the current config is kept.
+    fn reload_config(&mut self) {
+        if !self.config_watch.changed() {
+            return;
+        }
+
+        match load_config(self.config_watch.path.as_deref()) {
+            Ok(config) => {
+                // Values frozen by commands that were removed couldn't be
+                // unfrozen anymore.
+                self.freezer.unfreeze_all();
+                if config.settings.log_level.inner() != self.settings.log_level.inner() {
+                    info!("Changing the log level requires restarting the game");
+                }
+                self.apply_config(config);
+                self.config_err = None;
+                info!("Configuration reloaded");
+            },
+            Err(e) => {
+                error!("{}", e);
+                self.config_err = Some(config_error(&e));
+            },
+        }
+    }
+
+    /// Writes the runtime settings back to the config file, keeping the
+    /// rest of the file untouched.
+    fn save_settings(&mut self) {
+        // The file needs fixing first, and the settings in use may be the
+        // defaults, which would replace the user's own.
+        if self.config_err.is_some() {
+            return;
+        }
+
+        let result = self
+            .config_watch
+            .path
+            .as_deref()
+            .ok_or_else(|| "Couldn't find config file".to_string())
+            .and_then(|path| {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| format!("Couldn't read config file: {e}"))?;
+                let content = config::save_settings(&content, &self.settings)?;
+                std::fs::write(path, content)
+                    .map_err(|e| format!("Couldn't write config file: {e}"))
+            });
+        self.config_watch.mark_saved();
+
+        match result {
+            Ok(()) => info!("Settings saved"),
+            Err(e) => error!("Couldn't save settings: {}", e),
         }
     }
 
@@ -198,6 +432,8 @@ impl PracticeTool {
                     }
                 }
 
+                self.render_frozen(ui);
+
                 if ui.button_with_size("Close", [BUTTON_WIDTH * scaling_factor(ui), BUTTON_HEIGHT])
                 {
                     self.ui_state = UiState::Closed;
@@ -211,12 +447,65 @@ impl PracticeTool {
                     ])
                 {
                     self.ui_state = UiState::Closed;
-                    self.pointers.show_cursor.set(false);
+                    self.freezer.unfreeze_all();
+                    self.frame_advance.resume().ok();
+                    self.camera.free_cam.set(false).ok();
+                    if let Some(chr) = &self.pointers.chr {
+                        chr.no_collision.set(false).ok();
+                        chr.no_gravity.set(false).ok();
+                    }
+                    crate::revert_patches();
+                    self.pointers.show_cursor.set(false).ok();
                     hudhook::eject();
                 }
             });
     }
 
+    fn render_profile_selector(&mut self, ui: &Ui) {
+        let names = self.config.profile_names();
+        if names.len() < 2 {
+            return;
+        }
+
+        ui.same_line();
+
+        let width = names.iter().map(|name| ui.calc_text_size(name)[0]).fold(0., f32::max)
+            + ui.frame_height()
+            + ui.clone_style().frame_padding[0] * 2.;
+        let mut index = self.profile;
+        ui.set_next_item_width(width);
+        if ui.combo_simple_string("##profile", &mut index, &names) {
+            self.select_profile(index);
+        }
+        if let Some(key) = self.settings.cycle_profile {
+            if ui.is_item_hovered() {
+                ui.tooltip_text(format!("Cycle profiles with {key}"));
+            }
+        }
+    }
+
+    fn render_frozen(&mut self, ui: &Ui) {
+        let frozen = self.freezer.entries();
+        if frozen.is_empty() {
+            return;
+        }
+
+        ui.separator();
+        ui.text("Frozen values");
+        for entry in frozen {
+            if ui.small_button(format!("Unfreeze##{}", entry.label)) {
+                self.freezer.unfreeze(&entry.label);
+            }
+            ui.same_line();
+            if entry.error.is_some() {
+                ui.text_disabled(format!("{} = {} (unavailable)", entry.label, entry.value));
+            } else {
+                ui.text(format!("{} = {}", entry.label, entry.value));
+            }
+        }
+        ui.separator();
+    }
+
     fn render_closed(&mut self, ui: &Ui) {
         let [w, h] = ui.io().display_size;
 
@@ -255,7 +544,7 @@ impl PracticeTool {
                     .build(|| {
                         let style = ui.clone_style();
 
-                        self.pointers.show_cursor.set(true);
+                        self.pointers.show_cursor.set(true).ok();
 
                         ui.text(
                             "You can toggle indicators here, as\nwell as reset the frame \
@@ -265,14 +554,26 @@ impl PracticeTool {
                         ui.separator();
 
                         for indicator in &mut self.settings.indicators {
-                            let label = match indicator.indicator {
+                            if !indicator_available(&self.pointers, &indicator.indicator) {
+                                continue;
+                            }
+
+                            let label = match &indicator.indicator {
                                 IndicatorType::GameVersion => "Game Version",
                                 IndicatorType::Position => "Player Position",
                                 IndicatorType::PositionChange => "Player Velocity",
                                 IndicatorType::Igt => "IGT Timer",
                                 IndicatorType::Fps => "FPS",
                                 IndicatorType::FrameCount => "Frame Counter",
+                                IndicatorType::Hp => "HP",
+                                IndicatorType::Posture => "Posture",
+                                IndicatorType::Target => "Lock-on Target",
                                 IndicatorType::ImguiDebug => "ImGui Debug Info",
+                                IndicatorType::Pointer(name) => self
+                                    .user_pointers
+                                    .get(name)
+                                    .map(|p| p.label.as_str())
+                                    .unwrap_or(name),
                             };
 
                             let mut state = indicator.enabled;
@@ -304,9 +605,16 @@ impl PracticeTool {
                         let btn_close_width =
                             ui.content_region_max()[0] - style.frame_padding[0] * 2.0;
 
+                        {
+                            let _token = ui.begin_disabled(self.config_err.is_some());
+                            if ui.button_with_size("Save settings", [btn_close_width, 0.0]) {
+                                self.save_settings();
+                            }
+                        }
+
                         if ui.button_with_size("Close", [btn_close_width, 0.0]) {
                             ui.close_current_popup();
-                            self.pointers.show_cursor.set(false);
+                            self.pointers.show_cursor.set(false).ok();
                         }
                     });
 
@@ -355,6 +663,8 @@ impl PracticeTool {
                         }
                     });
 
+                self.render_profile_selector(ui);
+
                 ui.new_line();
 
                 for indicator in &self.settings.indicators {
@@ -362,12 +672,12 @@ impl PracticeTool {
                         continue;
                     }
 
-                    match indicator.indicator {
+                    match &indicator.indicator {
                         IndicatorType::GameVersion => {
                             ui.text(&self.version_label);
                         },
                         IndicatorType::Position => {
-                            if let Some([x, y, z, _]) = self.pointers.position.read() {
+                            if let Ok([x, y, z, _]) = self.pointers.position.read() {
                                 self.position_bufs.iter_mut().for_each(String::clear);
                                 write!(self.position_bufs[0], "{x:.3}").ok();
                                 write!(self.position_bufs[1], "{y:.3}").ok();
@@ -390,7 +700,7 @@ impl PracticeTool {
                             }
                         },
                         IndicatorType::PositionChange => {
-                            if let Some([x, y, z, _]) = self.pointers.position.read() {
+                            if let Ok([x, y, z, _]) = self.pointers.position.read() {
                                 let position_change_xyz = ((x - self.position_prev[0]).powf(2.0)
                                     + (y - self.position_prev[1]).powf(2.0)
                                     + (z - self.position_prev[2]).powf(2.0))
@@ -415,7 +725,7 @@ impl PracticeTool {
                             }
                         },
                         IndicatorType::Igt => {
-                            if let Some(igt) = self.pointers.igt.read() {
+                            if let Ok(igt) = self.pointers.igt.read() {
                                 let millis = (igt % 1000) / 10;
                                 let total_seconds = igt / 1000;
                                 let seconds = total_seconds % 60;
@@ -431,7 +741,7 @@ impl PracticeTool {
                             }
                         },
                         IndicatorType::Fps => {
-                            if let Some(fps) = self.pointers.fps.read() {
+                            if let Ok(fps) = self.pointers.fps.read() {
                                 self.fps_buf.clear();
                                 write!(self.fps_buf, "FPS {fps}",).ok();
                                 ui.text(&self.fps_buf);
@@ -442,9 +752,39 @@ impl PracticeTool {
                             write!(self.framecount_buf, "Frame count {0}", self.framecount,).ok();
                             ui.text(&self.framecount_buf);
                         },
+                        IndicatorType::Hp => {
+                            let Some(chr) = &self.pointers.chr else { continue };
+                            if let (Ok(hp), Ok(max_hp)) = (chr.hp.read(), chr.max_hp.read()) {
+                                self.hp_buf.clear();
+                                write!(self.hp_buf, "HP {hp} / {max_hp}").ok();
+                                ui.text(&self.hp_buf);
+                            }
+                        },
+                        IndicatorType::Posture => {
+                            let Some(chr) = &self.pointers.chr else { continue };
+                            if let (Ok(posture), Ok(max_posture)) =
+                                (chr.posture.read(), chr.max_posture.read())
+                            {
+                                self.posture_buf.clear();
+                                write!(self.posture_buf, "Posture {posture} / {max_posture}").ok();
+                                ui.text(&self.posture_buf);
+                            }
+                        },
+                        IndicatorType::Target => {
+                            let Some(chr) = &self.pointers.chr else { continue };
+                            render_target(ui, chr, &mut self.target_bufs);
+                        },
                         IndicatorType::ImguiDebug => {
                             imgui_debug(ui);
                         },
+                        IndicatorType::Pointer(name) => {
+                            let Some(pointer) = self.user_pointers.get(name) else { continue };
+                            if let Ok(value) = pointer.display() {
+                                self.pointer_buf.clear();
+                                write!(self.pointer_buf, "{} {value}", pointer.label).ok();
+                                ui.text(&self.pointer_buf);
+                            }
+                        },
                     }
                 }
 
@@ -531,10 +871,18 @@ impl ImguiRenderLoop for PracticeTool {
     fn render(&mut self, ui: &mut Ui) {
         let font_token = self.set_font(ui);
 
+        self.reload_config();
+
         let display = self.settings.display.is_pressed(ui);
         let hide = self.settings.hide.map(|k| k.is_pressed(ui)).unwrap_or(false);
+        let cycle_profile = self.settings.cycle_profile.map(|k| k.is_pressed(ui)).unwrap_or(false);
 
-        self.framecount += 1;
+        // While paused, only the frames stepped through are counted.
+        let tick = self.frame_advance.tick();
+        if self.frame_advance_errors.check_write("Frame advance", tick).unwrap_or(true) {
+            self.framecount += 1;
+        }
+        self.freezer.tick();
 
         if !ui.io().want_capture_keyboard && (display || hide) {
             self.ui_state = match (&self.ui_state, hide) {
@@ -551,6 +899,10 @@ impl ImguiRenderLoop for PracticeTool {
             // }
         }
 
+        if !ui.io().want_capture_keyboard && cycle_profile {
+            self.select_profile((self.profile + 1) % self.config.profile_names().len());
+        }
+
         match &self.ui_state {
             UiState::MenuOpen => {
                 // self.pointers.show_cursor.set(true);
@@ -570,6 +922,7 @@ impl ImguiRenderLoop for PracticeTool {
 
         let now = Instant::now();
         self.log.extend(self.log_rx.try_iter().inspect(|log| info!("{}", log)).map(|l| (now, l)));
+        self.log.extend(self.error_rx.try_iter().map(|l| (now, l)));
         self.log.retain(|(tm, _)| tm.elapsed() < Duration::from_secs(5));
 
         self.render_logs(ui);
@@ -598,6 +951,45 @@ impl ImguiRenderLoop for PracticeTool {
     }
 }
 
+/// Whether the indicator can be shown on the running game version.
+fn indicator_available(pointers: &Pointers, indicator: &IndicatorType) -> bool {
+    match indicator {
+        IndicatorType::Hp | IndicatorType::Posture | IndicatorType::Target => {
+            pointers.chr.is_some()
+        },
+        _ => true,
+    }
+}
+
+/// Shows the vitals of the locked-on enemy, if there is one.
+fn render_target(ui: &Ui, p: &ChrPointers, bufs: &mut [String; 3]) {
+    let (Ok(entity_id), Ok(param_id)) = (p.target_entity_id.read(), p.target_param_id.read())
+    else {
+        ui.text_disabled("No target");
+        return;
+    };
+
+    bufs.iter_mut().for_each(String::clear);
+    write!(bufs[0], "Target {entity_id} (param {param_id})").ok();
+    if let (Ok(hp), Ok(max_hp)) = (p.target_hp.read(), p.target_max_hp.read()) {
+        write!(bufs[1], "  HP {hp} / {max_hp}").ok();
+    }
+    if let (Ok(posture), Ok(max_posture), Ok(regen_delay)) =
+        (p.target_posture.read(), p.target_max_posture.read(), p.target_posture_regen_delay.read())
+    {
+        write!(bufs[2], "  Posture {posture} / {max_posture}").ok();
+        if regen_delay > 0. {
+            write!(bufs[2], " (regen in {regen_delay:.1}s)").ok();
+        } else {
+            write!(bufs[2], " (regenerating)").ok();
+        }
+    }
+
+    for buf in bufs.iter().filter(|buf| !buf.is_empty()) {
+        ui.text(buf);
+    }
+}
+
 // Display some imgui debug information. Very expensive.
 fn imgui_debug(ui: &Ui) {
     let io = ui.io();
@@ -6,25 +6,28 @@ use practice_tool_core::key::Key;
 use practice_tool_core::widgets::store_value::{ReadWrite, StoreValue};
 use practice_tool_core::widgets::Widget;
 
+use crate::widgets::ChainErrors;
+
 #[derive(Debug)]
-struct CycleColor {
-    ptr: PointerChain<i32>,
+struct CycleColor<M: MemoryBackend> {
+    ptr: PointerChain<i32, M>,
     values: Vec<i32>,
+    errors: ChainErrors,
     current: Option<i32>,
     label: String,
 }
 
-impl CycleColor {
-    fn new(values: &[i32], ptr: PointerChain<i32>) -> Self {
+impl<M: MemoryBackend> CycleColor<M> {
+    fn new(values: &[i32], ptr: PointerChain<i32, M>) -> Self {
         let mut values = values.to_vec();
         values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-        CycleColor { ptr, values, current: None, label: String::new() }
+        CycleColor { ptr, values, errors: Default::default(), current: None, label: String::new() }
     }
 }
 
-impl ReadWrite for CycleColor {
+impl<M: MemoryBackend> ReadWrite for CycleColor<M> {
     fn read(&mut self) -> bool {
-        self.current = self.ptr.read();
+        self.current = self.errors.check("Debug Color", self.ptr.read());
 
         self.label.clear();
 
@@ -42,7 +45,7 @@ impl ReadWrite for CycleColor {
             .and_then(|current| self.values.iter().find(|&&x| x > current))
             .unwrap_or_else(|| self.values.first().unwrap_or(&0));
 
-        self.ptr.write(next);
+        self.errors.check_write("Debug Color", self.ptr.write(next));
     }
 
     fn label(&self) -> &str {
@@ -50,9 +53,9 @@ impl ReadWrite for CycleColor {
     }
 }
 
-pub(crate) fn cycle_color(
+pub(crate) fn cycle_color<M: MemoryBackend>(
     values: &[i32],
-    ptr: PointerChain<i32>,
+    ptr: PointerChain<i32, M>,
     key: Option<Key>,
 ) -> Box<dyn Widget> {
     Box::new(StoreValue::new(CycleColor::new(values, ptr), key))
@@ -1,36 +1,48 @@
 use std::cmp::Ordering;
 use std::fmt::{Debug, Write};
 
+use libsekiro::backend::MemoryBackend;
 use libsekiro::memedit::PointerChain;
 use practice_tool_core::key::Key;
 use practice_tool_core::widgets::store_value::{ReadWrite, StoreValue};
 use practice_tool_core::widgets::Widget;
 
+use crate::widgets::ChainErrors;
+
 #[derive(Debug)]
-struct CycleSpeed {
-    ptr: PointerChain<f32>,
+struct CycleSpeed<M: MemoryBackend> {
+    name: &'static str,
+    ptr: PointerChain<f32, M>,
     values: Vec<f32>,
+    errors: ChainErrors,
     current: Option<f32>,
     label: String,
 }
 
-impl CycleSpeed {
-    fn new(values: &[f32], ptr: PointerChain<f32>) -> Self {
+impl<M: MemoryBackend> CycleSpeed<M> {
+    fn new(name: &'static str, values: &[f32], ptr: PointerChain<f32, M>) -> Self {
         let mut values = values.to_vec();
         values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-        CycleSpeed { ptr, values, current: None, label: String::new() }
+        CycleSpeed {
+            name,
+            ptr,
+            values,
+            errors: Default::default(),
+            current: None,
+            label: String::new(),
+        }
     }
 }
 
-impl ReadWrite for CycleSpeed {
+impl<M: MemoryBackend> ReadWrite for CycleSpeed<M> {
     fn read(&mut self) -> bool {
-        self.current = self.ptr.read();
+        self.current = self.errors.check(self.name, self.ptr.read());
 
         self.label.clear();
 
         match self.current {
-            Some(c) => write!(self.label, "Speed [{:.1}x]", c).ok(),
-            None => write!(self.label, "Speed").ok(),
+            Some(c) => write!(self.label, "{} [{:.1}x]", self.name, c).ok(),
+            None => write!(self.label, "{}", self.name).ok(),
         };
 
         self.current.is_some()
@@ -42,7 +54,7 @@ impl ReadWrite for CycleSpeed {
             .and_then(|current| self.values.iter().find(|&&x| x > current))
             .unwrap_or_else(|| self.values.first().unwrap_or(&1.0));
 
-        self.ptr.write(next);
+        self.errors.check_write(self.name, self.ptr.write(next));
     }
 
     fn label(&self) -> &str {
@@ -50,10 +62,38 @@ impl ReadWrite for CycleSpeed {
     }
 }
 
-pub(crate) fn cycle_speed(
+/// Cycles the speed multiplier pointed to by `ptr` through `values`.
+pub(crate) fn cycle_speed<M: MemoryBackend>(
+    name: &'static str,
     values: &[f32],
-    ptr: PointerChain<f32>,
+    ptr: PointerChain<f32, M>,
     key: Option<Key>,
 ) -> Box<dyn Widget> {
-    Box::new(StoreValue::new(CycleSpeed::new(values, ptr), key))
+    Box::new(StoreValue::new(CycleSpeed::new(name, values, ptr), key))
+}
+
+#[cfg(test)]
+mod tests {
+    use libsekiro::backend::FakeMemory;
+
+    use super::*;
+
+    #[test]
+    fn test_cycle_speed() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 1.0f32);
+
+        let mut cycle_speed = CycleSpeed::new(
+            "Speed",
+            &[2.0, 0.5, 1.0],
+            PointerChain::with_backend(memory.clone(), &[0x1000]),
+        );
+
+        for expected in [2.0, 0.5, 1.0] {
+            assert!(cycle_speed.read());
+            cycle_speed.write();
+            assert_eq!(memory.get::<f32>(0x1000), Some(expected));
+        }
+        assert_eq!(cycle_speed.label(), "Speed [0.5x]");
+    }
 }
@@ -0,0 +1,52 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::numeric::NumericChain;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_WIDTH};
+
+use crate::widgets::ChainErrors;
+
+struct EditValue<M: MemoryBackend> {
+    label: String,
+    chain: NumericChain<M>,
+    range: Option<(f64, f64)>,
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> Widget for EditValue<M> {
+    fn render(&mut self, ui: &Ui) {
+        let Some(mut value) = self.errors.check(&self.label, self.chain.read()) else {
+            ui.text_disabled(&self.label);
+            return;
+        };
+
+        let label_width =
+            ui.calc_text_size(&self.label)[0] + ui.clone_style().item_inner_spacing[0];
+        let _width = ui.push_item_width(BUTTON_WIDTH * scaling_factor(ui) - label_width);
+        let format = if self.chain.value_type().is_integer() { "%.0f" } else { "%.3f" };
+
+        let changed = match self.range {
+            Some((min, max)) => {
+                ui.slider_config(&self.label, min, max).display_format(format).build(&mut value)
+            },
+            None => ui
+                .input_scalar(&self.label, &mut value)
+                .display_format(format)
+                .enter_returns_true(true)
+                .build(),
+        };
+
+        if changed {
+            self.errors.check_write(&self.label, self.chain.write(value));
+        }
+    }
+}
+
+/// Edits the datum pointed to by `chain` with a slider if `range` is set,
+/// with an input field otherwise.
+pub(crate) fn edit_value<M: MemoryBackend>(
+    label: &str,
+    chain: NumericChain<M>,
+    range: Option<(f64, f64)>,
+) -> Box<dyn Widget> {
+    Box::new(EditValue { label: label.to_string(), chain, range, errors: Default::default() })
+}
@@ -0,0 +1,80 @@
+use libsekiro::backend::MemoryBackend;
+use libsekiro::event_flags::EventFlags;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::flag::{Flag, FlagWidget};
+use practice_tool_core::widgets::store_value::{ReadWrite, StoreValue};
+use practice_tool_core::widgets::Widget;
+
+use crate::widgets::ChainErrors;
+
+struct EventFlag<M: MemoryBackend> {
+    label: String,
+    id: u32,
+    event_flags: EventFlags<M>,
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> Flag for EventFlag<M> {
+    fn set(&mut self, value: bool) {
+        self.errors.check_write(&self.label, self.event_flags.set(self.id, value));
+    }
+
+    fn get(&self) -> Option<bool> {
+        self.errors.check(&self.label, self.event_flags.get(self.id))
+    }
+}
+
+pub(crate) fn event_flag<M: MemoryBackend>(
+    label: &str,
+    id: u32,
+    event_flags: EventFlags<M>,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    let flag = EventFlag { label: label.to_string(), id, event_flags, errors: Default::default() };
+    Box::new(FlagWidget::new(label, flag, key))
+}
+
+struct EventFlagPreset<M: MemoryBackend> {
+    label: String,
+    on: Vec<u32>,
+    off: Vec<u32>,
+    event_flags: EventFlags<M>,
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> ReadWrite for EventFlagPreset<M> {
+    fn read(&mut self) -> bool {
+        true
+    }
+
+    fn write(&mut self) {
+        let on = self.on.iter().map(|&id| (id, true));
+        let off = self.off.iter().map(|&id| (id, false));
+        let result = on.chain(off).try_for_each(|(id, value)| self.event_flags.set(id, value));
+        self.errors.check_write(&self.label, result);
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+/// Sets the flags in `on` and clears the ones in `off`.
+pub(crate) fn event_flag_preset<M: MemoryBackend>(
+    label: &str,
+    on: Vec<u32>,
+    off: Vec<u32>,
+    event_flags: EventFlags<M>,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    Box::new(StoreValue::new(
+        EventFlagPreset {
+            label: label.to_string(),
+            on,
+            off,
+            event_flags,
+            errors: Default::default(),
+        },
+        key,
+    ))
+}
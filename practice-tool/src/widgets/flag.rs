@@ -1,24 +1,58 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use libsekiro::backend::MemoryBackend;
 use libsekiro::memedit::Bitflag as BitflagInner;
 use practice_tool_core::key::Key;
 use practice_tool_core::widgets::flag::{Flag, FlagWidget};
 use practice_tool_core::widgets::Widget;
 
-struct Bitflag(BitflagInner<u8>);
+use crate::widgets::ChainErrors;
+
+struct Bitflag<T, M: MemoryBackend> {
+    label: String,
+    flag: BitflagInner<T, M>,
+    errors: ChainErrors,
+}
 
-impl Flag for Bitflag {
+impl<T, M> Flag for Bitflag<T, M>
+where
+    T: BitXor<Output = T>
+        + BitAnd<Output = T>
+        + BitOr<Output = T>
+        + Not<Output = T>
+        + PartialEq
+        + Copy
+        + Send
+        + Sync
+        + 'static,
+    M: MemoryBackend,
+{
     fn set(&mut self, value: bool) {
-        self.0.set(value);
+        self.errors.check_write(&self.label, self.flag.set(value));
     }
 
     fn get(&self) -> Option<bool> {
-        self.0.get()
+        self.errors.check(&self.label, self.flag.get())
     }
 }
 
-pub(crate) fn flag_widget(
+pub(crate) fn flag_widget<T, M>(
     label: &str,
-    bitflag: BitflagInner<u8>,
+    bitflag: BitflagInner<T, M>,
     key: Option<Key>,
-) -> Box<dyn Widget> {
-    Box::new(FlagWidget::new(label, Bitflag(bitflag), key))
+) -> Box<dyn Widget>
+where
+    T: BitXor<Output = T>
+        + BitAnd<Output = T>
+        + BitOr<Output = T>
+        + Not<Output = T>
+        + PartialEq
+        + Copy
+        + Send
+        + Sync
+        + 'static,
+    M: MemoryBackend,
+{
+    let flag = Bitflag { label: label.to_string(), flag: bitflag, errors: Default::default() };
+    Box::new(FlagWidget::new(label, flag, key))
 }
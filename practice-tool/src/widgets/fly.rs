@@ -0,0 +1,66 @@
+use hudhook::imgui::{Key as ImguiKey, Ui};
+use windows::Win32::UI::Input::XboxController::{XInputGetState, XINPUT_STATE};
+
+/// Sticks positions below this are ignored.
+const STICK_DEADZONE: f32 = 7849.;
+/// Triggers positions below this are ignored.
+const TRIGGER_THRESHOLD: f32 = 30.;
+/// Speed multiplier while shift is held.
+const FAST_MULTIPLIER: f32 = 4.;
+
+/// Reads the movement requested through the keyboard (WASD, space and
+/// ctrl, shift to go faster) or the first gamepad (left stick and
+/// triggers), as `[right, up, forward]` components between -1 and 1 (or
+/// more when going faster).
+pub(crate) fn fly_input(ui: &Ui) -> [f32; 3] {
+    let axis = |positive: ImguiKey, negative: ImguiKey| {
+        (ui.is_key_down(positive) as i32 - ui.is_key_down(negative) as i32) as f32
+    };
+
+    let mut input = [
+        axis(ImguiKey::D, ImguiKey::A),
+        axis(ImguiKey::Space, ImguiKey::LeftCtrl),
+        axis(ImguiKey::W, ImguiKey::S),
+    ];
+
+    let mut state = XINPUT_STATE::default();
+    if unsafe { XInputGetState(0, &mut state) } == 0 {
+        let gamepad = state.Gamepad;
+        let stick = |value: i16| {
+            let value = value as f32;
+            if value.abs() < STICK_DEADZONE {
+                0.
+            } else {
+                value / i16::MAX as f32
+            }
+        };
+        let trigger = |value: u8| {
+            let value = value as f32;
+            if value < TRIGGER_THRESHOLD {
+                0.
+            } else {
+                value / u8::MAX as f32
+            }
+        };
+
+        input[0] += stick(gamepad.sThumbLX);
+        input[1] += trigger(gamepad.bRightTrigger) - trigger(gamepad.bLeftTrigger);
+        input[2] += stick(gamepad.sThumbLY);
+    }
+
+    if ui.is_key_down(ImguiKey::LeftShift) {
+        input.iter_mut().for_each(|c| *c *= FAST_MULTIPLIER);
+    }
+
+    input
+}
+
+/// Moves `position` by `input` (see [`fly_input`]) along the axes of a
+/// camera matrix laid out as right, up and forward rows.
+pub(crate) fn fly(position: &mut [f32], matrix: &[f32; 16], input: [f32; 3], distance: f32) {
+    for (axis, amount) in matrix.chunks(4).take(3).zip(input) {
+        for (p, a) in position.iter_mut().zip(axis).take(3) {
+            *p += a * amount * distance;
+        }
+    }
+}
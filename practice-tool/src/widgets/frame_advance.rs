@@ -0,0 +1,88 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::frame_advance::FrameAdvance;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
+
+use crate::widgets::{key_label, ChainErrors};
+
+struct FrameAdvanceWidget<M: MemoryBackend> {
+    frame_advance: FrameAdvance<M>,
+    frames: u32,
+    key_pause: Option<Key>,
+    key_step: Option<Key>,
+    key_step_n: Option<Key>,
+    labels: [String; 4],
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> FrameAdvanceWidget<M> {
+    fn toggle_pause(&self) {
+        self.errors.check_write("Pause", self.frame_advance.toggle_pause());
+    }
+
+    fn step(&self, frames: u32) {
+        self.errors.check_write("Frame advance", self.frame_advance.step(frames));
+    }
+}
+
+impl<M: MemoryBackend> Widget for FrameAdvanceWidget<M> {
+    fn render(&mut self, ui: &Ui) {
+        let width = BUTTON_WIDTH * scaling_factor(ui);
+        let [pause, resume, step, step_n] = &self.labels;
+
+        let pause_label = if self.frame_advance.is_paused() { resume } else { pause };
+        if ui.button_with_size(pause_label, [width, BUTTON_HEIGHT]) {
+            self.toggle_pause();
+        }
+
+        let half_width = (width - ui.clone_style().item_spacing[0]) / 2.;
+        if ui.button_with_size(step, [half_width, BUTTON_HEIGHT]) {
+            self.step(1);
+        }
+        ui.same_line();
+        if ui.button_with_size(step_n, [half_width, BUTTON_HEIGHT]) {
+            self.step(self.frames);
+        }
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        let pressed = |key: Option<Key>| key.map(|k| k.is_pressed(ui)).unwrap_or(false);
+
+        if pressed(self.key_pause) {
+            self.toggle_pause();
+        }
+        if pressed(self.key_step) {
+            self.step(1);
+        }
+        if pressed(self.key_step_n) {
+            self.step(self.frames);
+        }
+    }
+}
+
+/// Pauses the game and steps it by one or `frames` frames.
+pub(crate) fn frame_advance<M: MemoryBackend>(
+    frame_advance: FrameAdvance<M>,
+    frames: u32,
+    key_pause: Option<Key>,
+    key_step: Option<Key>,
+    key_step_n: Option<Key>,
+) -> Box<dyn Widget> {
+    let labels = [
+        key_label("Pause", key_pause),
+        key_label("Resume", key_pause),
+        key_label("Step 1", key_step),
+        key_label(&format!("Step {frames}"), key_step_n),
+    ];
+
+    Box::new(FrameAdvanceWidget {
+        frame_advance,
+        frames,
+        key_pause,
+        key_step,
+        key_step_n,
+        labels,
+        errors: Default::default(),
+    })
+}
@@ -0,0 +1,105 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::pointers::CameraPointers;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
+
+use crate::widgets::fly::{fly, fly_input};
+use crate::widgets::{key_label, ChainErrors};
+
+struct FreeCam<M: MemoryBackend> {
+    camera: CameraPointers<M>,
+    speed: f32,
+    key_toggle: Option<Key>,
+    key_return: Option<Key>,
+    labels: [String; 3],
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> FreeCam<M> {
+    fn toggle(&self) {
+        let enabled = self.camera.free_cam.get().unwrap_or(false);
+        if !enabled {
+            // Start from where the player camera is rather than from wherever
+            // the free camera was left.
+            self.return_camera();
+        }
+        self.errors.check_write("Free camera", self.camera.free_cam.set(!enabled));
+    }
+
+    fn return_camera(&self) {
+        let result = self
+            .camera
+            .player_cam_matrix
+            .read()
+            .and_then(|matrix| self.camera.free_cam_matrix.write(matrix));
+        self.errors.check_write("Free camera", result);
+    }
+}
+
+impl<M: MemoryBackend> Widget for FreeCam<M> {
+    fn render(&mut self, ui: &Ui) {
+        let width = BUTTON_WIDTH * scaling_factor(ui);
+        let [off, on, ret] = &self.labels;
+        let enabled = self.errors.check("Free camera", self.camera.free_cam.get());
+
+        let label = if enabled == Some(true) { on } else { off };
+        if ui.button_with_size(label, [width, BUTTON_HEIGHT]) {
+            self.toggle();
+        }
+
+        ui.set_next_item_width(width);
+        ui.slider_config("##free_cam_speed", 1., 100.)
+            .display_format("Speed %.0f")
+            .build(&mut self.speed);
+
+        let _token = ui.begin_disabled(enabled != Some(true));
+        if ui.button_with_size(ret, [width, BUTTON_HEIGHT]) {
+            self.return_camera();
+        }
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        let pressed = |key: Option<Key>| key.map(|k| k.is_pressed(ui)).unwrap_or(false);
+
+        if pressed(self.key_toggle) {
+            self.toggle();
+        }
+        if pressed(self.key_return) {
+            self.return_camera();
+        }
+
+        if self.camera.free_cam.get() != Ok(true) {
+            return;
+        }
+
+        let input = fly_input(ui);
+        if input == [0.; 3] {
+            return;
+        }
+        let distance = self.speed * ui.io().delta_time;
+        let result = self.camera.free_cam_matrix.read().and_then(|mut matrix| {
+            let axes = matrix;
+            fly(&mut matrix[12..15], &axes, input, distance);
+            self.camera.free_cam_matrix.write(matrix)
+        });
+        self.errors.check_write("Free camera", result);
+    }
+}
+
+/// Detaches the camera from the player and flies it around at `speed`
+/// units per second.
+pub(crate) fn free_cam<M: MemoryBackend>(
+    camera: CameraPointers<M>,
+    speed: f32,
+    key_toggle: Option<Key>,
+    key_return: Option<Key>,
+) -> Box<dyn Widget> {
+    let labels = [
+        key_label("Free Camera [off]", key_toggle),
+        key_label("Free Camera [on]", key_toggle),
+        key_label("Return Camera", key_return),
+    ];
+
+    Box::new(FreeCam { camera, speed, key_toggle, key_return, labels, errors: Default::default() })
+}
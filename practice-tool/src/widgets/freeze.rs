@@ -0,0 +1,52 @@
+use libsekiro::prelude::*;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::flag::{Flag, FlagWidget};
+use practice_tool_core::widgets::Widget;
+
+use crate::widgets::ChainErrors;
+
+struct FreezeValue<M: MemoryBackend> {
+    label: String,
+    chain: NumericChain<M>,
+    value: Option<f64>,
+    freezer: Freezer,
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> Flag for FreezeValue<M> {
+    fn set(&mut self, value: bool) {
+        if value {
+            self.errors
+                .check(&self.label, self.chain.freeze(&self.freezer, &self.label, self.value));
+        } else {
+            self.freezer.unfreeze(&self.label);
+        }
+    }
+
+    fn get(&self) -> Option<bool> {
+        Some(self.freezer.is_frozen(&self.label))
+    }
+}
+
+/// Toggles freezing the datum pointed to by `chain` to `value`, or to the
+/// value it has when the widget is activated if `value` is `None`.
+pub(crate) fn freeze_widget<M: MemoryBackend>(
+    label: &str,
+    chain: NumericChain<M>,
+    value: Option<f64>,
+    freezer: &Freezer,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    let widget_label = match value {
+        Some(v) => format!("Freeze {label} [{}]", chain.value_type().format(v)),
+        None => format!("Freeze {label}"),
+    };
+    let freeze = FreezeValue {
+        label: label.to_string(),
+        chain,
+        value,
+        freezer: freezer.clone(),
+        errors: Default::default(),
+    };
+    Box::new(FlagWidget::new(&widget_label, freeze, key))
+}
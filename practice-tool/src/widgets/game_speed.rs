@@ -0,0 +1,61 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::frame_advance::FrameAdvance;
+use libsekiro::memedit::PointerChain;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_WIDTH};
+
+use crate::widgets::cycle_speed::cycle_speed;
+use crate::widgets::ChainErrors;
+
+struct GameSpeed<M: MemoryBackend> {
+    cycle: Box<dyn Widget>,
+    ptr: PointerChain<f32, M>,
+    frame_advance: FrameAdvance<M>,
+    range: (f32, f32),
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> Widget for GameSpeed<M> {
+    fn render(&mut self, ui: &Ui) {
+        // Frame advance sets the game speed every frame while paused.
+        let _paused = ui.begin_disabled(self.frame_advance.is_paused());
+        self.cycle.render(ui);
+
+        let Some(mut speed) = self.errors.check("Game Speed", self.ptr.read()) else {
+            return;
+        };
+        let (min, max) = self.range;
+        ui.set_next_item_width(BUTTON_WIDTH * scaling_factor(ui));
+        if ui.slider_config("##game_speed", min, max).display_format("%.2fx").build(&mut speed) {
+            self.errors.check_write("Game Speed", self.ptr.write(speed));
+        }
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        if !self.frame_advance.is_paused() {
+            self.cycle.interact(ui);
+        }
+    }
+}
+
+/// Cycles the global game speed through `values` with a button, and sets it
+/// to anything between the smallest and largest value with a slider. Both
+/// are disabled while `frame_advance` has the game paused.
+pub(crate) fn game_speed<M: MemoryBackend>(
+    values: &[f32],
+    ptr: PointerChain<f32, M>,
+    frame_advance: FrameAdvance<M>,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min).min(1.0);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max).max(1.0);
+
+    Box::new(GameSpeed {
+        cycle: cycle_speed("Game Speed", values, ptr.clone(), key),
+        ptr,
+        frame_advance,
+        range: (min, max),
+        errors: Default::default(),
+    })
+}
@@ -1,9 +1,115 @@
 pub(crate) mod cycle_color;
 pub(crate) mod cycle_speed;
+pub(crate) mod edit_value;
+pub(crate) mod event_flag;
 pub(crate) mod flag;
+pub(crate) mod fly;
+pub(crate) mod frame_advance;
+pub(crate) mod free_cam;
+pub(crate) mod freeze;
+pub(crate) mod game_speed;
 pub(crate) mod group;
 pub(crate) mod label;
+pub(crate) mod no_clip;
 pub(crate) mod nudge_pos;
 pub(crate) mod position;
+pub(crate) mod position_library;
 pub(crate) mod quitout;
+pub(crate) mod refill;
 pub(crate) mod savefile_manager;
+pub(crate) mod set_value;
+pub(crate) mod snapshot;
+pub(crate) mod teleport;
+pub(crate) mod toggle_value;
+pub(crate) mod warp;
+
+use std::sync::Mutex;
+
+use hudhook::imgui::Ui;
+use hudhook::tracing::{error, warn};
+use libsekiro::memedit::PointerChainError;
+use practice_tool_core::crossbeam_channel::Sender;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::Widget;
+
+/// Reports pointer chain errors of a widget to the log, once per failure
+/// instead of every frame: a chain is logged again only after it recovered
+/// or started failing in a different way.
+///
+/// A failed read, e.g. of a flag which moved in a game patch, is logged as a
+/// warning. A failed write means that something the user asked for didn't
+/// happen, and is logged as an error.
+#[derive(Debug, Default)]
+pub(crate) struct ChainErrors {
+    read: Mutex<Option<PointerChainError>>,
+    write: Mutex<Option<PointerChainError>>,
+}
+
+impl ChainErrors {
+    pub(crate) fn check<T>(&self, label: &str, result: Result<T, PointerChainError>) -> Option<T> {
+        Self::report(&self.read, result, |e| warn!("{label}: {e}"))
+    }
+
+    pub(crate) fn check_write<T>(
+        &self,
+        label: &str,
+        result: Result<T, PointerChainError>,
+    ) -> Option<T> {
+        Self::report(&self.write, result, |e| error!("{label}: {e}"))
+    }
+
+    fn report<T>(
+        last: &Mutex<Option<PointerChainError>>,
+        result: Result<T, PointerChainError>,
+        log: impl FnOnce(PointerChainError),
+    ) -> Option<T> {
+        match result {
+            Ok(value) => {
+                *last.lock().unwrap() = None;
+                Some(value)
+            },
+            Err(e) => {
+                if last.lock().unwrap().replace(e) != Some(e) {
+                    log(e);
+                }
+                None
+            },
+        }
+    }
+}
+
+/// Label of a button, with its hotkey if it has one.
+pub(crate) fn key_label(name: &str, key: Option<Key>) -> String {
+    match key {
+        Some(key) => format!("{name} ({key})"),
+        None => name.to_string(),
+    }
+}
+
+/// Renders a widget in an ID scope of its own, so that several instances
+/// of a widget in the same window don't share the state of their controls.
+struct IdScope(Box<dyn Widget>);
+
+impl Widget for IdScope {
+    fn render(&mut self, ui: &Ui) {
+        let _id = ui.push_id_ptr(self);
+        self.0.render(ui);
+    }
+
+    fn render_closed(&mut self, ui: &Ui) {
+        let _id = ui.push_id_ptr(self);
+        self.0.render_closed(ui);
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        self.0.interact(ui);
+    }
+
+    fn log(&mut self, tx: Sender<String>) {
+        self.0.log(tx);
+    }
+}
+
+pub(crate) fn id_scope(widget: Box<dyn Widget>) -> Box<dyn Widget> {
+    Box::new(IdScope(widget))
+}
@@ -0,0 +1,93 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::memedit::{Bitflag, PointerChain};
+use libsekiro::pointers::CameraPointers;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
+
+use crate::widgets::fly::{fly, fly_input};
+use crate::widgets::{key_label, ChainErrors};
+
+struct NoClip<M: MemoryBackend> {
+    position: PointerChain<[f32; 4], M>,
+    no_collision: Bitflag<u8, M>,
+    no_gravity: Bitflag<u8, M>,
+    camera: CameraPointers<M>,
+    speed: f32,
+    key_toggle: Option<Key>,
+    labels: [String; 2],
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> NoClip<M> {
+    fn toggle(&self) {
+        let enabled = !self.no_collision.get().unwrap_or(false);
+        let result = self.no_collision.set(enabled).and_then(|_| self.no_gravity.set(enabled));
+        self.errors.check_write("No clip", result);
+    }
+}
+
+impl<M: MemoryBackend> Widget for NoClip<M> {
+    fn render(&mut self, ui: &Ui) {
+        let width = BUTTON_WIDTH * scaling_factor(ui);
+        let [off, on] = &self.labels;
+        let enabled = self.errors.check("No clip", self.no_collision.get());
+
+        let label = if enabled == Some(true) { on } else { off };
+        if ui.button_with_size(label, [width, BUTTON_HEIGHT]) {
+            self.toggle();
+        }
+
+        ui.set_next_item_width(width);
+        ui.slider_config("##no_clip_speed", 1., 100.)
+            .display_format("Speed %.0f")
+            .build(&mut self.speed);
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        if self.key_toggle.map(|k| k.is_pressed(ui)).unwrap_or(false) {
+            self.toggle();
+        }
+
+        // While the free camera is on, the movement keys fly the camera.
+        if self.no_collision.get() != Ok(true) || self.camera.free_cam.get() == Ok(true) {
+            return;
+        }
+
+        let input = fly_input(ui);
+        if input == [0.; 3] {
+            return;
+        }
+        let distance = self.speed * ui.io().delta_time;
+        let result = self.camera.player_cam_matrix.read().and_then(|matrix| {
+            let mut position = self.position.read()?;
+            fly(&mut position, &matrix, input, distance);
+            self.position.write(position)
+        });
+        self.errors.check_write("No clip", result);
+    }
+}
+
+/// Turns off the player's collision and gravity, and flies them around at
+/// `speed` units per second relative to the camera.
+pub(crate) fn no_clip<M: MemoryBackend>(
+    position: PointerChain<[f32; 4], M>,
+    no_collision: Bitflag<u8, M>,
+    no_gravity: Bitflag<u8, M>,
+    camera: CameraPointers<M>,
+    speed: f32,
+    key_toggle: Option<Key>,
+) -> Box<dyn Widget> {
+    let labels = ["No Clip [off]", "No Clip [on]"].map(|label| key_label(label, key_toggle));
+
+    Box::new(NoClip {
+        position,
+        no_collision,
+        no_gravity,
+        camera,
+        speed,
+        key_toggle,
+        labels,
+        errors: Default::default(),
+    })
+}
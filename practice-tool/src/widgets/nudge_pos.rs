@@ -1,15 +1,129 @@
-use libsekiro::prelude::*;
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::memedit::PointerChain;
 use practice_tool_core::key::Key;
-use practice_tool_core::widgets::nudge_position::NudgePosition;
-use practice_tool_core::widgets::Widget;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
 
-use crate::widgets::position::SavePosition;
+use crate::widgets::{key_label, ChainErrors};
 
-pub(crate) fn nudge_position(
-    ptr: PointerChain<[f32; 4]>,
+/// Nudge directions as `[right, up, forward]` relative to the player.
+const DIRECTIONS: [(&str, [f32; 3]); 6] = [
+    ("Up", [0., 1., 0.]),
+    ("Down", [0., -1., 0.]),
+    ("Forward", [0., 0., 1.]),
+    ("Back", [0., 0., -1.]),
+    ("Left", [-1., 0., 0.]),
+    ("Right", [1., 0., 0.]),
+];
+
+/// Moves `position` by `distance` in `direction` (see [`DIRECTIONS`]),
+/// relative to the facing angle stored in its fourth component.
+fn nudge([x, y, z, angle]: [f32; 4], [right, up, forward]: [f32; 3], distance: f32) -> [f32; 4] {
+    let (sin, cos) = angle.sin_cos();
+    [
+        x + (forward * sin + right * cos) * distance,
+        y + up * distance,
+        z + (forward * cos - right * sin) * distance,
+        angle,
+    ]
+}
+
+/// Number of nudges a key held for `held` seconds should have triggered,
+/// repeating like typed characters do.
+fn repeats(ui: &Ui, held: f32) -> u32 {
+    let io = ui.io();
+    if held < io.key_repeat_delay {
+        0
+    } else {
+        ((held - io.key_repeat_delay) / io.key_repeat_rate) as u32 + 1
+    }
+}
+
+struct NudgePosition<M: MemoryBackend> {
+    ptr: PointerChain<[f32; 4], M>,
+    nudge: f32,
+    keys: [Option<Key>; 6],
+    held: [f32; 6],
+    labels: [String; 6],
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> NudgePosition<M> {
+    fn nudge(&self, direction: usize, count: u32) {
+        let (name, direction) = DIRECTIONS[direction];
+        let distance = self.nudge * count as f32;
+        let result =
+            self.ptr.read().and_then(|pos| self.ptr.write(nudge(pos, direction, distance)));
+        self.errors.check_write(&format!("Nudge {}", name.to_lowercase()), result);
+    }
+}
+
+impl<M: MemoryBackend> Widget for NudgePosition<M> {
+    fn render(&mut self, ui: &Ui) {
+        let width = BUTTON_WIDTH * scaling_factor(ui);
+        let half_width = (width - ui.clone_style().item_spacing[0]) / 2.;
+
+        for (i, label) in self.labels.iter().enumerate() {
+            if i % 2 == 1 {
+                ui.same_line();
+            }
+            if ui.button_with_size(label, [half_width, BUTTON_HEIGHT]) {
+                self.nudge(i, 1);
+            }
+        }
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        let delta_time = ui.io().delta_time;
+
+        for i in 0..DIRECTIONS.len() {
+            let Some(key) = self.keys[i] else { continue };
+
+            if key.is_pressed(ui) {
+                self.held[i] = 0.;
+                self.nudge(i, 1);
+            } else if key.is_down(ui) {
+                let before = repeats(ui, self.held[i]);
+                self.held[i] += delta_time;
+                let count = repeats(ui, self.held[i]) - before;
+                if count > 0 {
+                    self.nudge(i, count);
+                }
+            }
+        }
+    }
+}
+
+/// Nudges the player by `nudge` units up, down, or horizontally relative to
+/// where they are facing. Held hotkeys keep nudging.
+pub(crate) fn nudge_position<M: MemoryBackend>(
+    ptr: PointerChain<[f32; 4], M>,
     nudge: f32,
-    key_nudge_up: Option<Key>,
-    key_nudge_down: Option<Key>,
+    keys: [Option<Key>; 6],
 ) -> Box<dyn Widget> {
-    Box::new(NudgePosition::new(SavePosition::new(ptr, nudge), key_nudge_up, key_nudge_down))
+    let labels = std::array::from_fn(|i| key_label(DIRECTIONS[i].0, keys[i]));
+
+    Box::new(NudgePosition { ptr, nudge, keys, held: [0.; 6], labels, errors: Default::default() })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    #[test]
+    fn test_nudge() {
+        let close = |a: [f32; 4], b: [f32; 4]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5);
+
+        assert!(close(nudge([1., 2., 3., 0.], DIRECTIONS[0].1, 0.5), [1., 2.5, 3., 0.]));
+        assert!(close(nudge([1., 2., 3., 0.], DIRECTIONS[2].1, 2.), [1., 2., 5., 0.]));
+        assert!(close(nudge([1., 2., 3., 0.], DIRECTIONS[5].1, 2.), [3., 2., 3., 0.]));
+        assert!(close(nudge([0., 0., 0., FRAC_PI_2], DIRECTIONS[2].1, 1.), [
+            1., 0., 0., FRAC_PI_2
+        ]));
+        assert!(close(nudge([0., 0., 0., FRAC_PI_2], DIRECTIONS[4].1, 1.), [
+            0., 0., 1., FRAC_PI_2
+        ]));
+    }
 }
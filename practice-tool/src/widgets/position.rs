@@ -1,36 +1,60 @@
 use std::fmt::Write;
 
+use libsekiro::backend::MemoryBackend;
 use libsekiro::memedit::PointerChain;
 use practice_tool_core::key::Key;
-use practice_tool_core::widgets::nudge_position::NudgePositionStorage;
 use practice_tool_core::widgets::position::{Position, PositionStorage};
 use practice_tool_core::widgets::Widget;
 
-pub(super) struct SavePosition {
-    ptr: PointerChain<[f32; 4]>,
+use crate::widgets::ChainErrors;
+
+/// Formats a position as text that [`parse_position`] reads back, e.g. to
+/// share it through the clipboard.
+pub(super) fn format_position([x, y, z, angle]: [f32; 4]) -> String {
+    format!("{x:.3} {y:.3} {z:.3} {angle:.3}")
+}
+
+/// Parses a position written as three or four numbers separated by spaces
+/// and/or commas. The angle defaults to `angle` when missing.
+pub(super) fn parse_position(text: &str, angle: f32) -> Option<[f32; 4]> {
+    let values = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    match values[..] {
+        [x, y, z] => Some([x, y, z, angle]),
+        [x, y, z, angle] => Some([x, y, z, angle]),
+        _ => None,
+    }
+}
+
+struct SavePosition<M: MemoryBackend> {
+    ptr: PointerChain<[f32; 4], M>,
     saved_position: [f32; 4],
     label_current: String,
     label_stored: String,
     valid: bool,
-    nudge: f32,
+    errors: ChainErrors,
 }
 
-impl SavePosition {
-    pub(super) fn new(ptr: PointerChain<[f32; 4]>, nudge: f32) -> Self {
+impl<M: MemoryBackend> SavePosition<M> {
+    fn new(ptr: PointerChain<[f32; 4], M>) -> Self {
         Self {
             ptr,
             saved_position: [0.0; 4],
             label_current: String::new(),
             label_stored: String::new(),
             valid: false,
-            nudge,
+            errors: Default::default(),
         }
     }
 }
 
-impl PositionStorage for SavePosition {
+impl<M: MemoryBackend> PositionStorage for SavePosition<M> {
     fn save(&mut self) {
-        if let Some(pos) = self.ptr.read() {
+        if let Some(pos) = self.errors.check("Position", self.ptr.read()) {
             self.saved_position = pos;
             self.valid = true;
         } else {
@@ -39,13 +63,13 @@ impl PositionStorage for SavePosition {
     }
 
     fn load(&mut self) {
-        self.ptr.write(self.saved_position);
+        self.errors.check_write("Position", self.ptr.write(self.saved_position));
     }
 
     fn display_current(&mut self) -> &str {
         self.label_current.clear();
 
-        let pos = self.ptr.read();
+        let pos = self.errors.check("Position", self.ptr.read());
 
         let (read_pos, valid) = if let Some(pos) = pos { (pos, true) } else { ([0f32; 4], false) };
 
@@ -76,24 +100,25 @@ impl PositionStorage for SavePosition {
     }
 }
 
-impl NudgePositionStorage for SavePosition {
-    fn nudge_up(&mut self) {
-        if let Some([x, y, z, w]) = self.ptr.read() {
-            self.ptr.write([x, y + self.nudge, z, w]);
-        }
-    }
-
-    fn nudge_down(&mut self) {
-        if let Some([x, y, z, w]) = self.ptr.read() {
-            self.ptr.write([x, y - self.nudge, z, w]);
-        }
-    }
-}
-
-pub(crate) fn save_position(
-    ptr: PointerChain<[f32; 4]>,
+pub(crate) fn save_position<M: MemoryBackend>(
+    ptr: PointerChain<[f32; 4], M>,
     key_load: Option<Key>,
     key_save: Option<Key>,
 ) -> Box<dyn Widget> {
-    Box::new(Position::new(SavePosition::new(ptr, 0.0), key_load, key_save))
+    Box::new(Position::new(SavePosition::new(ptr), key_load, key_save))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_position() {
+        let position = [123.4, -56., 7.25, 1.5];
+        assert_eq!(parse_position(&format_position(position), 0.), Some(position));
+        assert_eq!(parse_position(" 123.4, -56.0,7.25 ", 1.5), Some(position));
+        assert_eq!(parse_position("123.4 -56.0", 0.), None);
+        assert_eq!(parse_position("123.4 -56.0 abc", 0.), None);
+        assert_eq!(parse_position("1 2 3 4 5", 0.), None);
+    }
 }
@@ -0,0 +1,223 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::memedit::PointerChain;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
+
+use crate::positions::{pack_path, PositionLibrary, SavedPosition};
+use crate::widgets::{key_label, ChainErrors};
+
+const POPUP: &str = "##position_library";
+const DEFAULT_PACK: &str = "position_pack.toml";
+
+struct PositionLibraryWidget<M: MemoryBackend> {
+    ptr: PointerChain<[f32; 4], M>,
+    library: PositionLibrary,
+    selected: Option<usize>,
+    name: String,
+    area: String,
+    notes: String,
+    pack: String,
+    status: Option<String>,
+    key_load: Option<Key>,
+    key_close: Key,
+    label: String,
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> PositionLibraryWidget<M> {
+    fn select(&mut self, index: usize) {
+        let position = &self.library.positions()[index];
+        self.name = position.name.clone();
+        self.area = position.area.clone();
+        self.notes = position.notes.clone();
+        self.selected = Some(index);
+    }
+
+    fn load(&self) {
+        if let Some(position) = self.selected.map(|i| &self.library.positions()[i]) {
+            self.errors.check_write("Position library", self.ptr.write(position.position));
+        }
+    }
+
+    fn save_current(&mut self) {
+        let Some(position) = self.errors.check("Position library", self.ptr.read()) else {
+            return;
+        };
+        let name = match self.name.trim() {
+            "" => format!("Position {}", self.library.positions().len() + 1),
+            name => name.to_string(),
+        };
+        let result = self.library.add(SavedPosition {
+            name,
+            area: self.area.trim().to_string(),
+            notes: self.notes.clone(),
+            position,
+        });
+        match result {
+            Ok(index) => self.select(index),
+            Err(e) => self.status = Some(e),
+        }
+    }
+
+    fn rename(&mut self) {
+        if let Some(index) = self.selected {
+            let result =
+                self.library.rename(index, self.name.trim(), self.area.trim(), &self.notes);
+            self.status = result.err();
+        }
+    }
+
+    fn delete(&mut self) {
+        if let Some(index) = self.selected.take() {
+            self.status = self.library.delete(index).err();
+        }
+    }
+
+    fn import(&mut self) {
+        self.status = Some(match pack_path(&self.pack) {
+            Some(path) => match self.library.import(&path) {
+                Ok(count) => {
+                    self.selected = None;
+                    format!("Imported {count} positions")
+                },
+                Err(e) => e,
+            },
+            None => "Couldn't find the pack file".to_string(),
+        });
+    }
+
+    fn export(&mut self) {
+        self.status = Some(match pack_path(&self.pack) {
+            Some(path) => match self.library.export(&path) {
+                Ok(()) => format!("Exported {} positions", self.library.positions().len()),
+                Err(e) => e,
+            },
+            None => "Couldn't find the pack file".to_string(),
+        });
+    }
+
+    fn render_browser(&mut self, ui: &Ui, width: f32) {
+        let mut clicked = None;
+        ui.child_window("##position_library_browser").size([width, 200.]).border(true).build(
+            || {
+                for (area, indices) in self.library.by_area() {
+                    let area = if area.is_empty() { "(no area)" } else { area };
+                    ui.tree_node_config(area).default_open(true).build(|| {
+                        for i in indices {
+                            let position = &self.library.positions()[i];
+                            if ui
+                                .selectable_config(format!("{}##{i}", position.name))
+                                .selected(self.selected == Some(i))
+                                .build()
+                            {
+                                clicked = Some(i);
+                            }
+                            if !position.notes.is_empty() && ui.is_item_hovered() {
+                                ui.tooltip_text(&position.notes);
+                            }
+                        }
+                    });
+                }
+            },
+        );
+        if let Some(i) = clicked {
+            self.select(i);
+        }
+    }
+}
+
+impl<M: MemoryBackend> Widget for PositionLibraryWidget<M> {
+    fn render(&mut self, ui: &Ui) {
+        let scale = scaling_factor(ui);
+        let width = BUTTON_WIDTH * scale;
+
+        if ui.button_with_size(&self.label, [width, BUTTON_HEIGHT]) {
+            ui.open_popup(POPUP);
+        }
+
+        ui.modal_popup_config(POPUP).resizable(false).movable(false).title_bar(false).build(|| {
+            let half_width = (width - ui.clone_style().item_spacing[0]) / 2.;
+
+            self.render_browser(ui, width);
+
+            ui.set_next_item_width(width);
+            ui.input_text("##name", &mut self.name).hint("Name").build();
+            ui.set_next_item_width(width);
+            ui.input_text("##area", &mut self.area).hint("Area").build();
+            ui.input_text_multiline("##notes", &mut self.notes, [width, 60. * scale]).build();
+
+            if ui.button_with_size("Save current", [half_width, BUTTON_HEIGHT]) {
+                self.save_current();
+            }
+            ui.same_line();
+            {
+                let _token = ui.begin_disabled(self.selected.is_none());
+                if ui.button_with_size("Load", [half_width, BUTTON_HEIGHT]) {
+                    self.load();
+                }
+                if ui.button_with_size("Rename", [half_width, BUTTON_HEIGHT]) {
+                    self.rename();
+                }
+                ui.same_line();
+                if ui.button_with_size("Delete", [half_width, BUTTON_HEIGHT]) {
+                    self.delete();
+                }
+            }
+
+            ui.separator();
+            ui.set_next_item_width(width);
+            ui.input_text("##pack", &mut self.pack).hint("Pack file").build();
+            if ui.button_with_size("Import", [half_width, BUTTON_HEIGHT]) {
+                self.import();
+            }
+            ui.same_line();
+            if ui.button_with_size("Export", [half_width, BUTTON_HEIGHT]) {
+                self.export();
+            }
+
+            if let Some(status) = &self.status {
+                ui.text_wrapped(status);
+            }
+
+            ui.separator();
+            if ui.button_with_size(format!("Close ({})", self.key_close), [width, BUTTON_HEIGHT])
+                || self.key_close.is_pressed(ui)
+            {
+                ui.close_current_popup();
+            }
+        });
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        if self.key_load.map(|k| k.is_pressed(ui)).unwrap_or(false) {
+            self.load();
+        }
+    }
+}
+
+/// Browses named positions persisted next to the DLL, grouped by area.
+pub(crate) fn position_library<M: MemoryBackend>(
+    ptr: PointerChain<[f32; 4], M>,
+    key_load: Option<Key>,
+    key_close: Key,
+) -> Box<dyn Widget> {
+    let library = PositionLibrary::load();
+    let status = library.load_error().map(str::to_string);
+    let label = key_label("Position Library", key_load);
+
+    Box::new(PositionLibraryWidget {
+        ptr,
+        library,
+        selected: None,
+        name: String::new(),
+        area: String::new(),
+        notes: String::new(),
+        pack: DEFAULT_PACK.to_string(),
+        status,
+        key_load,
+        key_close,
+        label,
+        errors: Default::default(),
+    })
+}
@@ -1,25 +1,29 @@
+use libsekiro::backend::MemoryBackend;
 use libsekiro::memedit::PointerChain;
 use practice_tool_core::key::Key;
 use practice_tool_core::widgets::store_value::{ReadWrite, StoreValue};
 use practice_tool_core::widgets::Widget;
 
-struct Quitout {
-    ptr: PointerChain<u8>,
+use crate::widgets::ChainErrors;
+
+struct Quitout<M: MemoryBackend> {
+    ptr: PointerChain<u8, M>,
+    errors: ChainErrors,
 }
 
-impl Quitout {
-    fn new(ptr: PointerChain<u8>) -> Self {
-        Self { ptr }
+impl<M: MemoryBackend> Quitout<M> {
+    fn new(ptr: PointerChain<u8, M>) -> Self {
+        Self { ptr, errors: Default::default() }
     }
 }
 
-impl ReadWrite for Quitout {
+impl<M: MemoryBackend> ReadWrite for Quitout<M> {
     fn read(&mut self) -> bool {
-        self.ptr.read().is_some()
+        self.errors.check("Quitout", self.ptr.read()).is_some()
     }
 
     fn write(&mut self) {
-        self.ptr.write(1);
+        self.errors.check_write("Quitout", self.ptr.write(1));
     }
 
     fn label(&self) -> &str {
@@ -27,6 +31,9 @@ impl ReadWrite for Quitout {
     }
 }
 
-pub(crate) fn quitout(ptr: PointerChain<u8>, key: Option<Key>) -> Box<dyn Widget> {
+pub(crate) fn quitout<M: MemoryBackend>(
+    ptr: PointerChain<u8, M>,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
     Box::new(StoreValue::new(Quitout::new(ptr), key))
 }
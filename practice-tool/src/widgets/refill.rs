@@ -0,0 +1,50 @@
+use libsekiro::backend::MemoryBackend;
+use libsekiro::memedit::PointerChain;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::store_value::{ReadWrite, StoreValue};
+use practice_tool_core::widgets::Widget;
+
+use crate::widgets::ChainErrors;
+
+#[derive(Debug)]
+struct Refill<M: MemoryBackend> {
+    name: String,
+    current: PointerChain<i32, M>,
+    max: PointerChain<i32, M>,
+    errors: ChainErrors,
+    label: String,
+}
+
+impl<M: MemoryBackend> ReadWrite for Refill<M> {
+    fn read(&mut self) -> bool {
+        true
+    }
+
+    fn write(&mut self) {
+        let refilled = self.max.read().and_then(|max| self.current.write(max));
+        self.errors.check_write(&self.name, refilled);
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+/// Sets the datum pointed to by `current` to the one pointed to by `max`.
+pub(crate) fn refill<M: MemoryBackend>(
+    name: &str,
+    current: PointerChain<i32, M>,
+    max: PointerChain<i32, M>,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    Box::new(StoreValue::new(
+        Refill {
+            name: name.to_string(),
+            current,
+            max,
+            errors: Default::default(),
+            label: format!("Refill {name}"),
+        },
+        key,
+    ))
+}
@@ -0,0 +1,50 @@
+use libsekiro::backend::MemoryBackend;
+use libsekiro::numeric::NumericChain;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::store_value::{ReadWrite, StoreValue};
+use practice_tool_core::widgets::Widget;
+
+use crate::widgets::ChainErrors;
+
+#[derive(Debug)]
+struct SetValue<M: MemoryBackend> {
+    name: String,
+    chain: NumericChain<M>,
+    value: f64,
+    errors: ChainErrors,
+    label: String,
+}
+
+impl<M: MemoryBackend> ReadWrite for SetValue<M> {
+    fn read(&mut self) -> bool {
+        true
+    }
+
+    fn write(&mut self) {
+        self.errors.check_write(&self.name, self.chain.write(self.value));
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+/// Writes `value` to the datum pointed to by `chain`.
+pub(crate) fn set_value<M: MemoryBackend>(
+    label: &str,
+    chain: NumericChain<M>,
+    value: f64,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    let widget_label = format!("Set {label} [{}]", chain.value_type().format(value));
+    Box::new(StoreValue::new(
+        SetValue {
+            name: label.to_string(),
+            chain,
+            value,
+            errors: Default::default(),
+            label: widget_label,
+        },
+        key,
+    ))
+}
@@ -0,0 +1,112 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::numeric::ValueType;
+use libsekiro::snapshot::Snapshot;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
+
+use crate::widgets::{key_label, ChainErrors};
+
+struct SnapshotWidget<M: MemoryBackend> {
+    label: String,
+    snapshot: Snapshot<M>,
+    values: Vec<(String, ValueType)>,
+    flags: Vec<String>,
+    key_load: Option<Key>,
+    key_save: Option<Key>,
+    labels: [String; 2],
+    summary: String,
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> SnapshotWidget<M> {
+    fn load(&self) {
+        self.errors.check_write(&self.label, self.snapshot.restore());
+    }
+
+    fn save(&mut self) {
+        if self.errors.check(&self.label, self.snapshot.capture()).is_some() {
+            self.summary = self.summarize();
+        }
+    }
+
+    /// Describes the captured state, one item per line.
+    fn summarize(&self) -> String {
+        let Some(state) = self.snapshot.captured() else {
+            return String::new();
+        };
+
+        let position =
+            state.position.map(|[x, y, z, a]| format!("Position {x:.1} {y:.1} {z:.1} {a:.1}"));
+        let values =
+            self.values.iter().zip(&state.values).map(|((label, value_type), &value)| {
+                format!("{label} {}", value_type.format(value))
+            });
+        let flags = self
+            .flags
+            .iter()
+            .zip(&state.flags)
+            .map(|(label, &on)| format!("{label} [{}]", if on { "on" } else { "off" }));
+
+        position.into_iter().chain(values).chain(flags).collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl<M: MemoryBackend> Widget for SnapshotWidget<M> {
+    fn render(&mut self, ui: &Ui) {
+        let width = BUTTON_WIDTH * scaling_factor(ui);
+        let half_width = (width - ui.clone_style().item_spacing[0]) / 2.;
+        let [load, save] = &self.labels;
+
+        {
+            let _token = ui.begin_disabled(!self.snapshot.is_captured());
+            if ui.button_with_size(load, [half_width, BUTTON_HEIGHT]) {
+                self.load();
+            }
+        }
+        if !self.summary.is_empty() && ui.is_item_hovered() {
+            ui.tooltip_text(&self.summary);
+        }
+        ui.same_line();
+        if ui.button_with_size(save, [half_width, BUTTON_HEIGHT]) {
+            self.save();
+        }
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        let pressed = |key: Option<Key>| key.map(|k| k.is_pressed(ui)).unwrap_or(false);
+
+        if pressed(self.key_load) {
+            self.load();
+        }
+        if pressed(self.key_save) {
+            self.save();
+        }
+    }
+}
+
+/// Saves and restores a snapshot of the player's state. `values` and `flags`
+/// hold the labels of what the snapshot captures, in the same order, for
+/// display.
+pub(crate) fn snapshot<M: MemoryBackend>(
+    label: &str,
+    snapshot: Snapshot<M>,
+    values: Vec<(String, ValueType)>,
+    flags: Vec<String>,
+    key_load: Option<Key>,
+    key_save: Option<Key>,
+) -> Box<dyn Widget> {
+    let labels = [key_label(label, key_load), key_label("Save", key_save)];
+
+    Box::new(SnapshotWidget {
+        label: label.to_string(),
+        snapshot,
+        values,
+        flags,
+        key_load,
+        key_save,
+        labels,
+        summary: String::new(),
+        errors: Default::default(),
+    })
+}
@@ -0,0 +1,74 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::memedit::PointerChain;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
+
+use crate::widgets::position::{format_position, parse_position};
+use crate::widgets::{key_label, ChainErrors};
+
+struct Teleport<M: MemoryBackend> {
+    ptr: PointerChain<[f32; 4], M>,
+    target: [f32; 4],
+    key: Option<Key>,
+    label: String,
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> Teleport<M> {
+    fn teleport(&self) {
+        self.errors.check_write("Teleport", self.ptr.write(self.target));
+    }
+
+    fn copy_current(&mut self) {
+        if let Some(position) = self.errors.check("Teleport", self.ptr.read()) {
+            self.target = position;
+        }
+    }
+}
+
+impl<M: MemoryBackend> Widget for Teleport<M> {
+    fn render(&mut self, ui: &Ui) {
+        let width = BUTTON_WIDTH * scaling_factor(ui);
+        let spacing = ui.clone_style().item_spacing[0];
+        let third_width = (width - 2. * spacing) / 3.;
+
+        ui.set_next_item_width(width);
+        ui.input_float4("##teleport", &mut self.target).display_format("%.1f").build();
+
+        if ui.button_with_size(&self.label, [width, BUTTON_HEIGHT]) {
+            self.teleport();
+        }
+
+        if ui.button_with_size("Current", [third_width, BUTTON_HEIGHT]) {
+            self.copy_current();
+        }
+        ui.same_line();
+        if ui.button_with_size("Copy", [third_width, BUTTON_HEIGHT]) {
+            ui.set_clipboard_text(format_position(self.target));
+        }
+        ui.same_line();
+        if ui.button_with_size("Paste", [third_width, BUTTON_HEIGHT]) {
+            let pasted = ui.clipboard_text().and_then(|text| parse_position(&text, self.target[3]));
+            if let Some(position) = pasted {
+                self.target = position;
+            }
+        }
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        if self.key.map(|k| k.is_pressed(ui)).unwrap_or(false) {
+            self.teleport();
+        }
+    }
+}
+
+/// Teleports the player to coordinates typed in or pasted as text.
+pub(crate) fn teleport<M: MemoryBackend>(
+    ptr: PointerChain<[f32; 4], M>,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    let label = key_label("Teleport", key);
+
+    Box::new(Teleport { ptr, target: [0.; 4], key, label, errors: Default::default() })
+}
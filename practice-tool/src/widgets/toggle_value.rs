@@ -0,0 +1,105 @@
+use std::fmt::Write;
+
+use libsekiro::backend::MemoryBackend;
+use libsekiro::numeric::NumericChain;
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::store_value::{ReadWrite, StoreValue};
+use practice_tool_core::widgets::Widget;
+
+use crate::widgets::ChainErrors;
+
+#[derive(Debug)]
+struct ToggleValue<M: MemoryBackend> {
+    name: String,
+    chain: NumericChain<M>,
+    values: [f64; 2],
+    current: Option<f64>,
+    errors: ChainErrors,
+    label: String,
+}
+
+impl<M: MemoryBackend> ToggleValue<M> {
+    fn new(name: &str, chain: NumericChain<M>, values: [f64; 2]) -> Self {
+        ToggleValue {
+            name: name.to_string(),
+            chain,
+            values,
+            current: None,
+            errors: Default::default(),
+            label: String::new(),
+        }
+    }
+}
+
+impl<M: MemoryBackend> ReadWrite for ToggleValue<M> {
+    fn read(&mut self) -> bool {
+        self.current = self.errors.check(&self.name, self.chain.read());
+
+        let value_type = self.chain.value_type();
+        self.label.clear();
+        match self.current {
+            Some(c) => write!(
+                self.label,
+                "{} [{}] ({} / {})",
+                self.name,
+                value_type.format(c),
+                value_type.format(self.values[0]),
+                value_type.format(self.values[1])
+            )
+            .ok(),
+            None => write!(self.label, "{}", self.name).ok(),
+        };
+
+        self.current.is_some()
+    }
+
+    /// Writes the second value if the first one is set, the first one
+    /// otherwise.
+    fn write(&mut self) {
+        let [first, second] = self.values;
+        let next = match self.current {
+            Some(c) if c == self.chain.value_type().cast(first) => second,
+            _ => first,
+        };
+
+        self.errors.check_write(&self.name, self.chain.write(next));
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+pub(crate) fn toggle_value<M: MemoryBackend>(
+    label: &str,
+    chain: NumericChain<M>,
+    values: [f64; 2],
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    Box::new(StoreValue::new(ToggleValue::new(label, chain, values), key))
+}
+
+#[cfg(test)]
+mod tests {
+    use libsekiro::backend::FakeMemory;
+    use libsekiro::memedit::PointerChain;
+
+    use super::*;
+
+    #[test]
+    fn test_toggle_value() {
+        let memory = FakeMemory::new();
+        memory.put(0x1000, 3.0f32);
+
+        let chain = PointerChain::<f32, _>::with_backend(memory.clone(), &[0x1000]);
+        let mut toggle_value = ToggleValue::new("Speed", chain.into(), [0.1, 5.0]);
+
+        for expected in [0.1, 5.0, 0.1] {
+            assert!(toggle_value.read());
+            toggle_value.write();
+            assert_eq!(memory.get::<f32>(0x1000), Some(expected));
+        }
+        assert!(toggle_value.read());
+        assert_eq!(toggle_value.label(), "Speed [0.100] (0.100 / 5.000)");
+    }
+}
@@ -0,0 +1,70 @@
+use hudhook::imgui::Ui;
+use libsekiro::backend::MemoryBackend;
+use libsekiro::warp::{Idol, Warp, AREAS};
+use practice_tool_core::key::Key;
+use practice_tool_core::widgets::{scaling_factor, Widget, BUTTON_HEIGHT, BUTTON_WIDTH};
+
+use crate::widgets::{key_label, ChainErrors};
+
+struct WarpWidget<M: MemoryBackend> {
+    warp: Warp<M>,
+    area: usize,
+    idol: usize,
+    key: Option<Key>,
+    label: String,
+    errors: ChainErrors,
+}
+
+impl<M: MemoryBackend> WarpWidget<M> {
+    fn selected(&self) -> &'static Idol {
+        Idol::in_area(AREAS[self.area]).nth(self.idol).unwrap()
+    }
+
+    fn warp(&self) {
+        let idol = self.selected();
+        self.errors.check_write(&format!("Warp to {}", idol.name), self.warp.warp(idol));
+    }
+}
+
+impl<M: MemoryBackend> Widget for WarpWidget<M> {
+    fn render(&mut self, ui: &Ui) {
+        let width = BUTTON_WIDTH * scaling_factor(ui);
+
+        ui.set_next_item_width(width);
+        if ui.combo_simple_string("##warp_area", &mut self.area, AREAS) {
+            self.idol = 0;
+        }
+
+        let idols: Vec<_> = Idol::in_area(AREAS[self.area]).map(|idol| idol.name).collect();
+        ui.set_next_item_width(width);
+        ui.combo_simple_string("##warp_idol", &mut self.idol, &idols);
+
+        if ui.button_with_size(&self.label, [width, BUTTON_HEIGHT]) {
+            self.warp();
+        }
+    }
+
+    fn interact(&mut self, ui: &Ui) {
+        if self.key.map(|key| key.is_pressed(ui)).unwrap_or(false) {
+            self.warp();
+        }
+    }
+}
+
+/// Warps to the idol picked from a list grouped by area, initially `idol`.
+pub(crate) fn warp<M: MemoryBackend>(
+    warp: Warp<M>,
+    idol: Option<&Idol>,
+    key: Option<Key>,
+) -> Box<dyn Widget> {
+    let (area, idol) = idol
+        .and_then(|idol| {
+            let area = AREAS.iter().position(|&area| area == idol.area)?;
+            let index = Idol::in_area(idol.area).position(|i| i == idol)?;
+            Some((area, index))
+        })
+        .unwrap_or((0, 0));
+    let label = key_label("Warp", key);
+
+    Box::new(WarpWidget { warp, area, idol, key, label, errors: Default::default() })
+}
@@ -1,5 +1,7 @@
 mod aob_scans;
+mod symbols;
 
 pub(crate) fn codegen() {
     aob_scans::get_base_addresses();
+    symbols::codegen_symbols();
 }
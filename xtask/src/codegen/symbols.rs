@@ -0,0 +1,58 @@
+use std::fmt::Write;
+use std::fs;
+
+use super::aob_scans::base_addresses_rs_path;
+
+/// Completes the generated `BaseAddresses` with `Clone` and `Default`
+/// derives and the lookup of its fields by name, which user-defined pointers
+/// and the runtime scan refer to.
+pub(crate) fn codegen_symbols() {
+    let path = base_addresses_rs_path();
+    let generated = fs::read_to_string(&path).expect("Couldn't read generated base addresses");
+    let mut generated = generated.replace(
+        "#[derive(Debug)]\npub struct BaseAddresses {",
+        "#[derive(Debug, Clone, Default)]\npub struct BaseAddresses {",
+    );
+
+    let fields = generated
+        .split_once("pub struct BaseAddresses {")
+        .and_then(|(_, rest)| rest.split_once('}'))
+        .map(|(fields, _)| fields)
+        .expect("Couldn't find the generated BaseAddresses struct")
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("pub ")?.split_once(':'))
+        .map(|(name, _)| name.to_string())
+        .collect::<Vec<_>>();
+
+    generated.push_str("impl BaseAddresses {\n");
+    generated
+        .push_str("    /// Names of the base addresses, as accepted by [`BaseAddresses::get`].\n");
+    generated.push_str("    pub const SYMBOLS: &'static [&'static str] = &[\n");
+    for name in &fields {
+        writeln!(generated, "        \"{name}\",").unwrap();
+    }
+    generated.push_str("    ];\n\n");
+
+    generated.push_str("    /// Looks up a base address by the name of its field, e.g.\n");
+    generated.push_str("    /// `\"player_position\"`.\n");
+    generated.push_str("    pub fn get(&self, name: &str) -> Option<usize> {\n");
+    generated.push_str("        match name {\n");
+    for name in &fields {
+        writeln!(generated, "            \"{name}\" => Some(self.{name}),").unwrap();
+    }
+    generated.push_str("            _ => None,\n");
+    generated.push_str("        }\n");
+    generated.push_str("    }\n\n");
+
+    generated.push_str("    pub fn get_mut(&mut self, name: &str) -> Option<&mut usize> {\n");
+    generated.push_str("        match name {\n");
+    for name in &fields {
+        writeln!(generated, "            \"{name}\" => Some(&mut self.{name}),").unwrap();
+    }
+    generated.push_str("            _ => None,\n");
+    generated.push_str("        }\n");
+    generated.push_str("    }\n");
+    generated.push_str("}\n");
+
+    fs::write(&path, generated).expect("Couldn't write generated base addresses");
+}